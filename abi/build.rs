@@ -8,24 +8,36 @@ fn main() {
     tonic_build::configure()
         .out_dir(path)
         .with_sqlx_type(&["reservation.ReservationStatus"])
+        .with_serde(&["reservation.OpeningHours"], true, true)
         .with_derive_builder(&[
             "reservation.ReservationQuery",
             "reservation.ReservationFilter",
+            "reservation.HistoryRequest",
+            "reservation.AvailabilityRequest",
         ])
         .with_derive_builder_into(
             "reservation.ReservationQuery",
-            &["resource_id", "user_id", "status", "page", "desc"],
+            &["resource_id", "user_id", "page", "desc", "mode", "group_id"],
         )
         .with_derive_builder_into(
             "reservation.ReservationFilter",
-            &["resource_id", "user_id", "status", "desc"],
+            &["resource_id", "user_id", "desc", "group_id"],
+        )
+        .with_derive_builder_into("reservation.HistoryRequest", &["id", "desc"])
+        .with_derive_builder_into(
+            "reservation.AvailabilityRequest",
+            &["resource_ids", "min_duration_secs", "group_id"],
         )
         .with_derive_builder_option("reservation.ReservationFilter", &["cursor"])
+        .with_derive_builder_option("reservation.HistoryRequest", &["cursor"])
         .with_derive_builder_option("reservation.ReservationQuery", &["start", "end"])
+        .with_derive_builder_option("reservation.AvailabilityRequest", &["start", "end"])
         .with_type_attributes(
             &[
                 "reservation.ReservationFilter",
                 "reservation.ReservationQuery",
+                "reservation.HistoryRequest",
+                "reservation.AvailabilityRequest",
             ],
             &[r#"#[builder(build_fn(name = "private_build"))]"#],
         )
@@ -33,6 +45,13 @@ fn main() {
             &["page_size"],
             &["#[builder(setter(into), default = \"10\")]"],
         )
+        .with_field_attributes(
+            &[
+                "reservation.ReservationQuery.statuses",
+                "reservation.ReservationFilter.statuses",
+            ],
+            &[r#"#[builder(setter(into, each(name = "status")), default)]"#],
+        )
         .compile(&["protos/reservation.proto"], &["protos"])
         .unwrap();
 
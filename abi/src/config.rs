@@ -8,6 +8,8 @@ use crate::Error;
 pub struct Config {
     pub db: DbConfig,
     pub server: ServerConfig,
+    #[serde(default)]
+    pub hold: HoldConfig,
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
@@ -32,6 +34,30 @@ pub struct ServerConfig {
     pub port: u16,
 }
 
+/// expiry of pending reservations
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HoldConfig {
+    /// default ttl of pending reservations in seconds, 0 means they never expire
+    #[serde(default)]
+    pub ttl_secs: u64,
+    /// how often expired pending reservations are cancelled, in seconds
+    #[serde(default = "default_reap_interval")]
+    pub reap_interval_secs: u64,
+}
+
+fn default_reap_interval() -> u64 {
+    30
+}
+
+impl Default for HoldConfig {
+    fn default() -> Self {
+        Self {
+            ttl_secs: 0,
+            reap_interval_secs: default_reap_interval(),
+        }
+    }
+}
+
 impl ServerConfig {
     pub fn url(&self, https: bool) -> String {
         if https {
@@ -86,7 +112,11 @@ mod tests {
                 server: ServerConfig {
                     host: "0.0.0.0".to_string(),
                     port: 50001
-                }
+                },
+                hold: HoldConfig {
+                    ttl_secs: 0,
+                    reap_interval_secs: 30,
+                },
             }
         )
     }
@@ -7,12 +7,33 @@ use regex::Regex;
 pub enum ReservationConflictInfo {
     Parsed(ReservationConflict),
     Unparsed(String),
+    Full(ResourceFull),
+    Unavailable(ResourceUnavailable),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ReservationConflict {
     pub new: ReservationWindow,
     pub old: ReservationWindow,
+    /// id of the existing reservation, the database error does not carry it
+    pub conflicting_id: Option<i64>,
+}
+
+/// the resource has no capacity left for the new reservation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceFull {
+    pub new: ReservationWindow,
+    /// the first interval in which the resource already has `capacity` reservations
+    pub full: ReservationWindow,
+    pub capacity: i32,
+}
+
+/// the resource does not take the new reservation, it is closed or blacked out in the window
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceUnavailable {
+    pub new: ReservationWindow,
+    /// the calendar rule the reservation violates
+    pub reason: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -61,6 +82,7 @@ impl TryFrom<ParsedInfo> for ReservationConflict {
         Ok(Self {
             new: value.new.try_into()?,
             old: value.old.try_into()?,
+            conflicting_id: None,
         })
     }
 }
@@ -175,7 +197,7 @@ mod tests {
                 assert_eq!(conflict.old.start.to_rfc3339(), "2022-12-25T22:00:00+00:00");
                 assert_eq!(conflict.old.end.to_rfc3339(), "2022-12-28T19:00:00+00:00");
             }
-            ReservationConflictInfo::Unparsed(_) => panic!("should be parsed"),
+            _ => panic!("should be parsed"),
         }
     }
 }
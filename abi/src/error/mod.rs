@@ -1,8 +1,14 @@
 mod conflict;
 
+use chrono::{DateTime, Utc};
 use sqlx::postgres::PgDatabaseError;
 
-pub use conflict::{ReservationConflict, ReservationConflictInfo, ReservationWindow};
+use crate::{ConflictDetail, ReservationStatus};
+
+pub use conflict::{
+    ReservationConflict, ReservationConflictInfo, ReservationWindow, ResourceFull,
+    ResourceUnavailable,
+};
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -30,9 +36,24 @@ pub enum Error {
     #[error("Conflict reservation")]
     ConflictReservation(ReservationConflictInfo),
 
+    #[error("Conflict reservations")]
+    ConflictReservations(Vec<ReservationConflictInfo>),
+
     #[error("Invalid resource id: {0}")]
     InvalidResourceId(String),
 
+    #[error("Unknown or inactive resource: {0}")]
+    UnknownResource(String),
+
+    #[error("Resource already exists: {0}")]
+    ResourceExists(String),
+
+    #[error("Resource {0} is closed at {1}, outside of its opening hours")]
+    ResourceClosed(String, DateTime<Utc>),
+
+    #[error("Resource {0} is blacked out from {1} to {2}: {3}")]
+    ResourceBlackedOut(String, DateTime<Utc>, DateTime<Utc>, String),
+
     #[error("Invalid page size: {0}")]
     InvalidPageSize(i64),
 
@@ -42,6 +63,57 @@ pub enum Error {
     #[error("Invalid status: {0}")]
     InvalidStatus(i32),
 
+    #[error("Invalid consumer id: {0}")]
+    InvalidConsumerId(String),
+
+    #[error("Invalid update type: {0}")]
+    InvalidUpdateType(i32),
+
+    #[error("Invalid query mode: {0}")]
+    InvalidQueryMode(i32),
+
+    #[error("Invalid duration: {0}")]
+    InvalidDuration(i64),
+
+    #[error("Invalid recurrence: {0}")]
+    InvalidRecurrence(String),
+
+    #[error("Invalid idempotency key: {0}")]
+    InvalidIdempotencyKey(String),
+
+    #[error("Invalid series id: {0}")]
+    InvalidSeriesId(String),
+
+    #[error("Invalid series scope: {0}")]
+    InvalidSeriesScope(i32),
+
+    #[error("Invalid capacity: {0}")]
+    InvalidCapacity(i32),
+
+    #[error("Invalid parent resource: {0}")]
+    InvalidParent(String),
+
+    #[error("Invalid timezone: {0}")]
+    InvalidTimezone(String),
+
+    #[error("Invalid opening hours on weekday {0}")]
+    InvalidOpeningHours(i32),
+
+    #[error("Version mismatch: expected {0}, actual {1}")]
+    VersionMismatch(i64, i64),
+
+    #[error("Illegal status transition from {0} to {1}")]
+    IllegalTransition(ReservationStatus, ReservationStatus),
+
+    #[error("Reservation {0} is cancelled and can't be moved")]
+    ReservationCancelled(i64),
+
+    #[error("Hold of reservation {0} has expired")]
+    HoldExpired(i64),
+
+    #[error("Aborted since other operations in the batch failed")]
+    Aborted,
+
     #[error("Unknown error")]
     Unknown,
 }
@@ -54,8 +126,38 @@ impl PartialEq for Error {
             (Self::InvalidUserId(v1), Self::InvalidUserId(v2)) => v1 == v2,
             (Self::InvalidReservationId(v1), Self::InvalidReservationId(v2)) => v1 == v2,
             (Self::ConflictReservation(v1), Self::ConflictReservation(v2)) => v1 == v2,
+            (Self::ConflictReservations(v1), Self::ConflictReservations(v2)) => v1 == v2,
             (Self::InvalidResourceId(v1), Self::InvalidResourceId(v2)) => v1 == v2,
+            (Self::UnknownResource(v1), Self::UnknownResource(v2)) => v1 == v2,
+            (Self::ResourceExists(v1), Self::ResourceExists(v2)) => v1 == v2,
+            (Self::InvalidConsumerId(v1), Self::InvalidConsumerId(v2)) => v1 == v2,
+            (Self::InvalidPageSize(v1), Self::InvalidPageSize(v2)) => v1 == v2,
+            (Self::InvalidCursor(v1), Self::InvalidCursor(v2)) => v1 == v2,
+            (Self::InvalidStatus(v1), Self::InvalidStatus(v2)) => v1 == v2,
+            (Self::InvalidUpdateType(v1), Self::InvalidUpdateType(v2)) => v1 == v2,
+            (Self::InvalidQueryMode(v1), Self::InvalidQueryMode(v2)) => v1 == v2,
+            (Self::InvalidDuration(v1), Self::InvalidDuration(v2)) => v1 == v2,
+            (Self::InvalidRecurrence(v1), Self::InvalidRecurrence(v2)) => v1 == v2,
+            (Self::InvalidIdempotencyKey(v1), Self::InvalidIdempotencyKey(v2)) => v1 == v2,
+            (Self::InvalidSeriesId(v1), Self::InvalidSeriesId(v2)) => v1 == v2,
+            (Self::InvalidSeriesScope(v1), Self::InvalidSeriesScope(v2)) => v1 == v2,
+            (Self::InvalidCapacity(v1), Self::InvalidCapacity(v2)) => v1 == v2,
+            (Self::InvalidParent(v1), Self::InvalidParent(v2)) => v1 == v2,
+            (Self::InvalidTimezone(v1), Self::InvalidTimezone(v2)) => v1 == v2,
+            (Self::InvalidOpeningHours(v1), Self::InvalidOpeningHours(v2)) => v1 == v2,
+            (Self::ResourceClosed(r1, t1), Self::ResourceClosed(r2, t2)) => r1 == r2 && t1 == t2,
+            (
+                Self::ResourceBlackedOut(r1, s1, e1, m1),
+                Self::ResourceBlackedOut(r2, s2, e2, m2),
+            ) => r1 == r2 && s1 == s2 && e1 == e2 && m1 == m2,
+            (Self::VersionMismatch(e1, a1), Self::VersionMismatch(e2, a2)) => e1 == e2 && a1 == a2,
+            (Self::IllegalTransition(f1, t1), Self::IllegalTransition(f2, t2)) => {
+                f1 == f2 && t1 == t2
+            }
+            (Self::ReservationCancelled(v1), Self::ReservationCancelled(v2)) => v1 == v2,
+            (Self::HoldExpired(v1), Self::HoldExpired(v2)) => v1 == v2,
             (Self::NotFound, Self::NotFound) => true,
+            (Self::Aborted, Self::Aborted) => true,
             (Self::Unknown, Self::Unknown) => true,
             (Self::InvalidTime, Self::InvalidTime) => true,
             _ => false,
@@ -95,12 +197,44 @@ impl From<Error> for tonic::Status {
             | Error::InvalidResourceId(_)
             | Error::InvalidPageSize(_)
             | Error::InvalidCursor(_)
-            | Error::InvalidStatus(_) => tonic::Status::invalid_argument(e.to_string()),
+            | Error::InvalidStatus(_)
+            | Error::InvalidConsumerId(_)
+            | Error::InvalidUpdateType(_)
+            | Error::InvalidQueryMode(_)
+            | Error::InvalidDuration(_)
+            | Error::InvalidRecurrence(_)
+            | Error::InvalidIdempotencyKey(_)
+            | Error::InvalidSeriesId(_)
+            | Error::InvalidSeriesScope(_)
+            | Error::InvalidCapacity(_)
+            | Error::InvalidParent(_)
+            | Error::InvalidTimezone(_)
+            | Error::InvalidOpeningHours(_) => tonic::Status::invalid_argument(e.to_string()),
 
             Error::ConflictReservation(info) => {
-                tonic::Status::failed_precondition(format!("Conflict reservation: {}", info))
+                ConflictDetail::from(&info).into_status(format!("Conflict reservation: {}", info))
+            }
+            Error::ConflictReservations(infos) => {
+                let message = infos
+                    .iter()
+                    .map(|info| info.to_string())
+                    .collect::<Vec<_>>()
+                    .join("; ");
+                ConflictDetail::into_status_all(
+                    infos.iter().map(ConflictDetail::from).collect(),
+                    format!("Conflict reservations: {}", message),
+                )
             }
+            Error::IllegalTransition(..)
+            | Error::ReservationCancelled(_)
+            | Error::HoldExpired(_)
+            | Error::VersionMismatch(..)
+            | Error::ResourceClosed(..)
+            | Error::ResourceBlackedOut(..) => tonic::Status::failed_precondition(e.to_string()),
             Error::NotFound => tonic::Status::not_found("No reservation found by given condition"),
+            Error::UnknownResource(_) => tonic::Status::not_found(e.to_string()),
+            Error::ResourceExists(_) => tonic::Status::already_exists(e.to_string()),
+            Error::Aborted => tonic::Status::aborted(e.to_string()),
             Error::Unknown => tonic::Status::unknown("unknown error"),
         }
     }
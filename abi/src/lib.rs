@@ -5,10 +5,15 @@ mod pb;
 mod types;
 mod utils;
 
+use sqlx::{Postgres, QueryBuilder};
+
 pub use config::*;
 pub use utils::*;
 
-pub use error::{Error, ReservationConflict, ReservationConflictInfo, ReservationWindow};
+pub use error::{
+    Error, ReservationConflict, ReservationConflictInfo, ReservationWindow, ResourceFull,
+    ResourceUnavailable,
+};
 pub use pb::*;
 
 /// validate the data structure, raise error if invalid
@@ -29,8 +34,10 @@ pub trait Normalizer: Validator {
     fn do_normalize(&mut self);
 }
 
+/// build the sql statement, user provided values are bound as arguments instead of being
+/// spliced into the statement
 pub trait ToSql {
-    fn to_sql(&self) -> String;
+    fn to_sql(&self) -> QueryBuilder<'_, Postgres>;
 }
 
 /// database equivalent of the "reservation_status" enum
@@ -41,6 +48,17 @@ pub enum RsvpStatus {
     Confirmed,
     Pending,
     Blocked,
+    Cancelled,
+}
+
+/// database equivalent of the "reservation_update_type" enum
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "reservation_update_type", rename_all = "lowercase")]
+pub enum RsvpUpdateType {
+    Unknown,
+    Create,
+    Update,
+    Delete,
 }
 
 pub type ReservationId = i64;
@@ -1,5 +1,5 @@
 /// Core reservation object. Contains all the information for a reservation
-/// if ListenResponse op is DELETE, only id will be populated
+/// if ListenResponse op is DELETE, reservation is the last snapshot before it was deleted
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Reservation {
@@ -24,6 +24,25 @@ pub struct Reservation {
     /// extra note
     #[prost(string, tag = "7")]
     pub note: ::prost::alloc::string::String,
+    /// when the reservation was cancelled, empty if it is not cancelled
+    #[prost(message, optional, tag = "8")]
+    pub cancelled_at: ::core::option::Option<::prost_types::Timestamp>,
+    /// reason given when cancelling the reservation
+    #[prost(string, tag = "9")]
+    pub cancel_reason: ::prost::alloc::string::String,
+    /// how long a pending reservation holds its slot in seconds, if 0 use the server default.
+    /// only used when making the reservation, it is not stored
+    #[prost(int64, tag = "10")]
+    pub hold_ttl_secs: i64,
+    /// when the pending hold expires and the reservation gets cancelled, empty if it never expires
+    #[prost(message, optional, tag = "11")]
+    pub expires_at: ::core::option::Option<::prost_types::Timestamp>,
+    /// recurring series the reservation belongs to, empty if it is not recurring
+    #[prost(string, tag = "12")]
+    pub series_id: ::prost::alloc::string::String,
+    /// starts from 1 and is incremented on every change of the reservation
+    #[prost(int64, tag = "13")]
+    pub version: i64,
 }
 /// To make a reservation, send a ReservationRequest with Reservation object (id should be empty)
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -31,6 +50,14 @@ pub struct Reservation {
 pub struct ReserveRequest {
     #[prost(message, optional, tag = "1")]
     pub reservation: ::core::option::Option<Reservation>,
+    /// optional key chosen by the client, at most 64 characters. Retrying with the same key
+    /// returns the reservation made by the first attempt instead of making another one
+    #[prost(string, tag = "2")]
+    pub idempotency_key: ::prost::alloc::string::String,
+    /// if set, reserve the first member of the group free for the window, in id order.
+    /// The resource_id of the reservation is ignored, the returned one is the chosen member
+    #[prost(string, tag = "3")]
+    pub group_id: ::prost::alloc::string::String,
 }
 /// Created reservation will be returned in ReserveResponse
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -39,6 +66,99 @@ pub struct ReserveResponse {
     #[prost(message, optional, tag = "1")]
     pub reservation: ::core::option::Option<Reservation>,
 }
+/// To reserve whichever of several interchangeable resources is free, send a ReserveAnyRequest
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReserveAnyRequest {
+    /// the reservation to make, its resource_id is ignored
+    #[prost(message, optional, tag = "1")]
+    pub reservation: ::core::option::Option<Reservation>,
+    /// candidate resources, the first one free for the window is reserved
+    #[prost(string, repeated, tag = "2")]
+    pub resource_ids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// optional key, same as the idempotency_key of ReserveRequest
+    #[prost(string, tag = "3")]
+    pub idempotency_key: ::prost::alloc::string::String,
+}
+/// Created reservation will be returned in ReserveAnyResponse, its resource_id is the chosen one
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReserveAnyResponse {
+    #[prost(message, optional, tag = "1")]
+    pub reservation: ::core::option::Option<Reservation>,
+}
+/// To make several reservations at once, send a ReserveManyRequest.
+/// Either all of them are made or none is
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReserveManyRequest {
+    #[prost(message, repeated, tag = "1")]
+    pub reservations: ::prost::alloc::vec::Vec<Reservation>,
+}
+/// Created reservations will be returned in the order of the request
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReserveManyResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub reservations: ::prost::alloc::vec::Vec<Reservation>,
+}
+/// To make a recurring reservation, send a ReserveRecurringRequest
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReserveRecurringRequest {
+    /// template for the occurrences, start and end are the window of the first occurrence
+    #[prost(message, optional, tag = "1")]
+    pub reservation: ::core::option::Option<Reservation>,
+    /// iCalendar RRULE value, e.g. "FREQ=WEEKLY;BYDAY=MO,WE;COUNT=10". DTSTART is the template start
+    /// in the local time of timezone
+    #[prost(string, tag = "2")]
+    pub rrule: ::prost::alloc::string::String,
+    /// start times of the occurrences to skip
+    #[prost(message, repeated, tag = "3")]
+    pub exdates: ::prost::alloc::vec::Vec<::prost_types::Timestamp>,
+    /// how occurrences that conflict are handled
+    #[prost(enumeration = "RecurrenceMode", tag = "4")]
+    pub mode: i32,
+    /// IANA timezone the rule is expanded in, e.g. "America/Los_Angeles", so that occurrences keep
+    /// their local time of day and weekday across daylight saving changes. Empty is UTC
+    #[prost(string, tag = "5")]
+    pub timezone: ::prost::alloc::string::String,
+}
+/// an occurrence of a recurring reservation
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Occurrence {
+    /// the occurrence, id is 0 if it is not reserved
+    #[prost(message, optional, tag = "1")]
+    pub reservation: ::core::option::Option<Reservation>,
+    /// why the occurrence could not be reserved, empty if it doesn't conflict
+    #[prost(message, optional, tag = "2")]
+    pub conflict: ::core::option::Option<ConflictDetail>,
+}
+/// Occurrences are returned in the order of their start time
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReserveRecurringResponse {
+    /// series id shared by the reserved occurrences, empty if nothing is reserved
+    #[prost(string, tag = "1")]
+    pub series_id: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "2")]
+    pub occurrences: ::prost::alloc::vec::Vec<Occurrence>,
+}
+/// To check whether a reservation could be made without making it, send a CheckRequest
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CheckRequest {
+    #[prost(message, optional, tag = "1")]
+    pub reservation: ::core::option::Option<Reservation>,
+}
+/// Existing reservations conflicting with the checked one, empty if it could be made
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CheckResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub conflicts: ::prost::alloc::vec::Vec<Reservation>,
+}
 /// To update a reservation, send an UpdateRequest. Only note is updatable
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -47,6 +167,9 @@ pub struct UpdateRequest {
     pub id: i64,
     #[prost(string, tag = "2")]
     pub note: ::prost::alloc::string::String,
+    /// if not 0, the note is only updated if the reservation is still at this version
+    #[prost(int64, tag = "3")]
+    pub expected_version: i64,
 }
 /// Updated reservation will be returned in UpdateResponse
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -55,12 +178,35 @@ pub struct UpdateResponse {
     #[prost(message, optional, tag = "1")]
     pub reservation: ::core::option::Option<Reservation>,
 }
+/// To move a reservation to another time window, send a RescheduleRequest. The id is kept
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RescheduleRequest {
+    #[prost(int64, tag = "1")]
+    pub id: i64,
+    /// new start time for the reservation
+    #[prost(message, optional, tag = "2")]
+    pub start: ::core::option::Option<::prost_types::Timestamp>,
+    /// new end time for the reservation
+    #[prost(message, optional, tag = "3")]
+    pub end: ::core::option::Option<::prost_types::Timestamp>,
+}
+/// Rescheduled reservation will be returned in RescheduleResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RescheduleResponse {
+    #[prost(message, optional, tag = "1")]
+    pub reservation: ::core::option::Option<Reservation>,
+}
 /// To change a reservation from pending to confirmed, send a ConfirmRequest
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ConfirmRequest {
     #[prost(int64, tag = "1")]
     pub id: i64,
+    /// if not 0, the reservation is only confirmed if it is still at this version
+    #[prost(int64, tag = "2")]
+    pub expected_version: i64,
 }
 /// Confirmed reservation will be returned in ConfirmResponse
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -69,12 +215,38 @@ pub struct ConfirmResponse {
     #[prost(message, optional, tag = "1")]
     pub reservation: ::core::option::Option<Reservation>,
 }
-/// To cancel a reservation, send a CancelRequest
+/// To move a reservation to another status, send a TransitionRequest
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TransitionRequest {
+    #[prost(int64, tag = "1")]
+    pub id: i64,
+    /// target status, must be reachable from the current status
+    #[prost(enumeration = "ReservationStatus", tag = "2")]
+    pub status: i32,
+    /// if not 0, the reservation is only moved if it is still at this version
+    #[prost(int64, tag = "3")]
+    pub expected_version: i64,
+}
+/// Reservation in its new status will be returned in TransitionResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TransitionResponse {
+    #[prost(message, optional, tag = "1")]
+    pub reservation: ::core::option::Option<Reservation>,
+}
+/// To cancel a reservation, send a CancelRequest. Cancelled reservation is kept but frees its time window
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CancelRequest {
     #[prost(int64, tag = "1")]
     pub id: i64,
+    /// optional reason for the cancellation
+    #[prost(string, tag = "2")]
+    pub reason: ::prost::alloc::string::String,
+    /// if not 0, the reservation is only cancelled if it is still at this version
+    #[prost(int64, tag = "3")]
+    pub expected_version: i64,
 }
 /// Cancel reservation will be returned in CancelResponse
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -83,6 +255,119 @@ pub struct CancelResponse {
     #[prost(message, optional, tag = "1")]
     pub reservation: ::core::option::Option<Reservation>,
 }
+/// occurrences of a recurring series, cancelled occurrences are never selected
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SeriesSelector {
+    #[prost(string, tag = "1")]
+    pub series_id: ::prost::alloc::string::String,
+    /// the occurrence the scope is relative to, could be empty if scope is ALL
+    #[prost(int64, tag = "2")]
+    pub id: i64,
+    #[prost(enumeration = "SeriesScope", tag = "3")]
+    pub scope: i32,
+}
+/// To update the note of occurrences in a series, send an UpdateSeriesRequest
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateSeriesRequest {
+    #[prost(message, optional, tag = "1")]
+    pub series: ::core::option::Option<SeriesSelector>,
+    #[prost(string, tag = "2")]
+    pub note: ::prost::alloc::string::String,
+}
+/// Updated occurrences will be returned in the order of their start time
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateSeriesResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub reservations: ::prost::alloc::vec::Vec<Reservation>,
+}
+/// To cancel occurrences in a series, send a CancelSeriesRequest
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CancelSeriesRequest {
+    #[prost(message, optional, tag = "1")]
+    pub series: ::core::option::Option<SeriesSelector>,
+    /// optional reason for the cancellation
+    #[prost(string, tag = "2")]
+    pub reason: ::prost::alloc::string::String,
+}
+/// Cancelled occurrences will be returned in the order of their start time
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CancelSeriesResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub reservations: ::prost::alloc::vec::Vec<Reservation>,
+}
+/// To move occurrences in a series by a fixed offset, send a ShiftSeriesRequest.
+/// Nothing is moved if any occurrence conflicts after the shift
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ShiftSeriesRequest {
+    #[prost(message, optional, tag = "1")]
+    pub series: ::core::option::Option<SeriesSelector>,
+    /// offset in seconds, negative to move the occurrences earlier
+    #[prost(int64, tag = "2")]
+    pub offset_secs: i64,
+}
+/// Shifted occurrences will be returned in the order of their start time
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ShiftSeriesResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub reservations: ::prost::alloc::vec::Vec<Reservation>,
+}
+/// result of one id in a batch operation
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReservationResult {
+    #[prost(int64, tag = "1")]
+    pub id: i64,
+    /// the reservation, empty if the operation failed on it
+    #[prost(message, optional, tag = "2")]
+    pub reservation: ::core::option::Option<Reservation>,
+    /// why the operation failed on it, empty on success
+    #[prost(message, optional, tag = "3")]
+    pub error: ::core::option::Option<RpcStatus>,
+}
+/// To confirm several pending reservations, send a ConfirmManyRequest
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ConfirmManyRequest {
+    #[prost(int64, repeated, tag = "1")]
+    pub ids: ::prost::alloc::vec::Vec<i64>,
+    /// if true nothing is confirmed when any of them fails, the others fail with ABORTED
+    #[prost(bool, tag = "2")]
+    pub transactional: bool,
+}
+/// Results will be returned in the order of the ids
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ConfirmManyResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub results: ::prost::alloc::vec::Vec<ReservationResult>,
+}
+/// To cancel several reservations, send a CancelManyRequest
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CancelManyRequest {
+    #[prost(int64, repeated, tag = "1")]
+    pub ids: ::prost::alloc::vec::Vec<i64>,
+    /// optional reason for the cancellation
+    #[prost(string, tag = "2")]
+    pub reason: ::prost::alloc::string::String,
+    /// if true nothing is cancelled when any of them fails, the others fail with ABORTED
+    #[prost(bool, tag = "3")]
+    pub transactional: bool,
+}
+/// Results will be returned in the order of the ids
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CancelManyResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub results: ::prost::alloc::vec::Vec<ReservationResult>,
+}
 /// To get a reservation, send a GetRequest
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -97,6 +382,20 @@ pub struct GetResponse {
     #[prost(message, optional, tag = "1")]
     pub reservation: ::core::option::Option<Reservation>,
 }
+/// To get several reservations, send a GetManyRequest
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetManyRequest {
+    #[prost(int64, repeated, tag = "1")]
+    pub ids: ::prost::alloc::vec::Vec<i64>,
+}
+/// Results will be returned in the order of the ids
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetManyResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub results: ::prost::alloc::vec::Vec<ReservationResult>,
+}
 /// Query reservation with user id, resource id, start time, end time, and status
 #[derive(derive_builder::Builder)]
 #[builder(build_fn(name = "private_build"))]
@@ -111,10 +410,10 @@ pub struct ReservationQuery {
     #[prost(string, tag = "2")]
     #[builder(setter(into), default)]
     pub user_id: ::prost::alloc::string::String,
-    /// use status to filter result. If UNKNOWN, return all reservations
-    #[prost(enumeration = "ReservationStatus", tag = "3")]
-    #[builder(setter(into), default)]
-    pub status: i32,
+    /// use statuses to filter result. If empty or contains UNKNOWN, return all reservations
+    #[prost(enumeration = "ReservationStatus", repeated, tag = "3")]
+    #[builder(setter(into, each(name = "status")), default)]
+    pub statuses: ::prost::alloc::vec::Vec<i32>,
     /// start time for the reservation query, if 0, use Infinty for start time
     #[prost(message, optional, tag = "4")]
     #[builder(setter(into, strip_option), default)]
@@ -127,6 +426,14 @@ pub struct ReservationQuery {
     #[prost(bool, tag = "6")]
     #[builder(setter(into), default)]
     pub desc: bool,
+    /// how reservations are matched against the time window, default is CONTAINED
+    #[prost(enumeration = "ReservationQueryMode", tag = "7")]
+    #[builder(setter(into), default)]
+    pub mode: i32,
+    /// only reservations of the members of the group. If empty, no group filter
+    #[prost(string, tag = "8")]
+    #[builder(setter(into), default)]
+    pub group_id: ::prost::alloc::string::String,
 }
 /// To query reservation, send a QueryRequest
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -149,10 +456,10 @@ pub struct ReservationFilter {
     #[prost(string, tag = "2")]
     #[builder(setter(into), default)]
     pub user_id: ::prost::alloc::string::String,
-    /// use status to filter result. If UNKNOWN, return all reservations
-    #[prost(enumeration = "ReservationStatus", tag = "3")]
-    #[builder(setter(into), default)]
-    pub status: i32,
+    /// use statuses to filter result. If empty or contains UNKNOWN, return all reservations
+    #[prost(enumeration = "ReservationStatus", repeated, tag = "3")]
+    #[builder(setter(into, each(name = "status")), default)]
+    pub statuses: ::prost::alloc::vec::Vec<i32>,
     /// cursor
     #[prost(int64, optional, tag = "4")]
     #[builder(setter(into, strip_option), default)]
@@ -165,6 +472,10 @@ pub struct ReservationFilter {
     #[prost(bool, tag = "6")]
     #[builder(setter(into), default)]
     pub desc: bool,
+    /// only reservations of the members of the group. If empty, no group filter
+    #[prost(string, tag = "7")]
+    #[builder(setter(into), default)]
+    pub group_id: ::prost::alloc::string::String,
 }
 /// To query reservations, send a FilterRequest
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -195,7 +506,36 @@ pub struct FilterResponse {
 /// Client can listen to reservation updates by sending a ListenRequest
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct ListenRequest {}
+pub struct ListenRequest {
+    /// consumer id. If set, server resumes after the last change the consumer acknowledged with ack,
+    /// so a reconnecting consumer receives every change it missed
+    #[prost(string, optional, tag = "1")]
+    pub consumer_id: ::core::option::Option<::prost::alloc::string::String>,
+    /// last change id the client has seen. If set, server sends every change after it,
+    /// regardless of the cursor recorded for consumer_id. 0 replays every change, any other
+    /// id must be an existing change
+    #[prost(int64, optional, tag = "2")]
+    pub last_change_id: ::core::option::Option<i64>,
+    /// only send changes for these resources. If empty, send changes for all resources
+    #[prost(string, repeated, tag = "3")]
+    pub resource_ids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// only send changes for resources whose id starts with the prefix. If empty, no prefix filter
+    #[prost(string, tag = "4")]
+    pub resource_id_prefix: ::prost::alloc::string::String,
+    /// only send changes for the user. If empty, send changes for all users
+    #[prost(string, tag = "5")]
+    pub user_id: ::prost::alloc::string::String,
+    /// only send changes of these update types. If empty, send all types of changes
+    #[prost(enumeration = "ReservationUpdateType", repeated, tag = "6")]
+    pub ops: ::prost::alloc::vec::Vec<i32>,
+    /// only send changes whose reservation is in these statuses. If empty, send all statuses
+    #[prost(enumeration = "ReservationStatus", repeated, tag = "7")]
+    pub statuses: ::prost::alloc::vec::Vec<i32>,
+    /// only send changes for the members of the group, as they are when the change is sent.
+    /// If empty, no group filter
+    #[prost(string, tag = "8")]
+    pub group_id: ::prost::alloc::string::String,
+}
 /// Server will send ListenResponse to client in streaming response
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -203,180 +543,646 @@ pub struct ListenResponse {
     /// update type
     #[prost(enumeration = "ReservationUpdateType", tag = "1")]
     pub op: i32,
-    /// id for updated reservation
+    /// updated reservation
     #[prost(message, optional, tag = "2")]
     pub reservation: ::core::option::Option<Reservation>,
+    /// id of the change, could be used as last_change_id to resume listening
+    #[prost(int64, tag = "3")]
+    pub change_id: i64,
 }
-/// reservation status for a given time period
-#[derive(
-    sqlx::Type, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration,
-)]
-#[repr(i32)]
-pub enum ReservationStatus {
-    Unknown = 0,
-    Pending = 1,
-    Confirmed = 2,
-    Blocked = 3,
+/// To record that a consumer has received every change up to change_id, send an AckRequest
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AckRequest {
+    #[prost(string, tag = "1")]
+    pub consumer_id: ::prost::alloc::string::String,
+    /// change_id of the last ListenResponse the consumer has handled
+    #[prost(int64, tag = "2")]
+    pub change_id: i64,
 }
-impl ReservationStatus {
-    /// String value of the enum field names used in the ProtoBuf definition.
-    ///
-    /// The values are not transformed in any way and thus are considered stable
-    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
-    pub fn as_str_name(&self) -> &'static str {
-        match self {
-            ReservationStatus::Unknown => "RESERVATION_STATUS_UNKNOWN",
-            ReservationStatus::Pending => "RESERVATION_STATUS_PENDING",
-            ReservationStatus::Confirmed => "RESERVATION_STATUS_CONFIRMED",
-            ReservationStatus::Blocked => "RESERVATION_STATUS_BLOCKED",
-        }
-    }
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AckResponse {}
+/// a change of a reservation, recorded in rsvp.reservation_changes
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReservationChange {
+    /// id of the change
+    #[prost(int64, tag = "1")]
+    pub id: i64,
+    /// update type
+    #[prost(enumeration = "ReservationUpdateType", tag = "2")]
+    pub op: i32,
+    /// reservation before the change, empty if op is CREATE
+    #[prost(message, optional, tag = "3")]
+    pub old: ::core::option::Option<Reservation>,
+    /// reservation after the change, empty if op is DELETE
+    #[prost(message, optional, tag = "4")]
+    pub new: ::core::option::Option<Reservation>,
 }
-/// when reservation is updated, record the update type
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
-#[repr(i32)]
-pub enum ReservationUpdateType {
-    Unknown = 0,
-    Create = 1,
-    Update = 2,
-    Delete = 3,
+/// To get the change history of a reservation, send a HistoryRequest. Changes are ordered by change id
+#[derive(derive_builder::Builder)]
+#[builder(build_fn(name = "private_build"))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HistoryRequest {
+    /// reservation id
+    #[prost(int64, tag = "1")]
+    #[builder(setter(into), default)]
+    pub id: i64,
+    /// cursor
+    #[prost(int64, optional, tag = "2")]
+    #[builder(setter(into, strip_option), default)]
+    pub cursor: ::core::option::Option<i64>,
+    /// page size for the query
+    #[prost(int64, tag = "3")]
+    #[builder(setter(into), default = "10")]
+    pub page_size: i64,
+    /// sort direction
+    #[prost(bool, tag = "4")]
+    #[builder(setter(into), default)]
+    pub desc: bool,
 }
-impl ReservationUpdateType {
-    /// String value of the enum field names used in the ProtoBuf definition.
-    ///
-    /// The values are not transformed in any way and thus are considered stable
-    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
-    pub fn as_str_name(&self) -> &'static str {
-        match self {
-            ReservationUpdateType::Unknown => "RESERVATION_UPDATE_TYPE_UNKNOWN",
-            ReservationUpdateType::Create => "RESERVATION_UPDATE_TYPE_CREATE",
-            ReservationUpdateType::Update => "RESERVATION_UPDATE_TYPE_UPDATE",
-            ReservationUpdateType::Delete => "RESERVATION_UPDATE_TYPE_DELETE",
-        }
-    }
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HistoryResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub changes: ::prost::alloc::vec::Vec<ReservationChange>,
+    #[prost(message, optional, tag = "2")]
+    pub pager: ::core::option::Option<FilterPager>,
 }
-/// Generated client implementations.
-pub mod reservation_service_client {
-    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
-    use tonic::codegen::http::Uri;
-    use tonic::codegen::*;
-    /// Reservation service
-    #[derive(Debug, Clone)]
-    pub struct ReservationServiceClient<T> {
-        inner: tonic::client::Grpc<T>,
-    }
-    impl ReservationServiceClient<tonic::transport::Channel> {
-        /// Attempt to create a new client by connecting to a given endpoint.
-        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
-        where
-            D: std::convert::TryInto<tonic::transport::Endpoint>,
-            D::Error: Into<StdError>,
-        {
-            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
-            Ok(Self::new(conn))
-        }
-    }
-    impl<T> ReservationServiceClient<T>
-    where
-        T: tonic::client::GrpcService<tonic::body::BoxBody>,
-        T::Error: Into<StdError>,
-        T::ResponseBody: Body<Data = Bytes> + Send + 'static,
-        <T::ResponseBody as Body>::Error: Into<StdError> + Send,
-    {
-        pub fn new(inner: T) -> Self {
-            let inner = tonic::client::Grpc::new(inner);
-            Self { inner }
-        }
-        pub fn with_origin(inner: T, origin: Uri) -> Self {
-            let inner = tonic::client::Grpc::with_origin(inner, origin);
-            Self { inner }
+/// To find when resources are free, send an AvailabilityRequest
+#[derive(derive_builder::Builder)]
+#[builder(build_fn(name = "private_build"))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AvailabilityRequest {
+    /// resources to search, at least one of resource_ids or group_id is required
+    #[prost(string, repeated, tag = "1")]
+    #[builder(setter(into), default)]
+    pub resource_ids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// start time of the search window
+    #[prost(message, optional, tag = "2")]
+    #[builder(setter(into, strip_option), default)]
+    pub start: ::core::option::Option<::prost_types::Timestamp>,
+    /// end time of the search window
+    #[prost(message, optional, tag = "3")]
+    #[builder(setter(into, strip_option), default)]
+    pub end: ::core::option::Option<::prost_types::Timestamp>,
+    /// minimum length of a free slot in seconds. If 0, return every free slot
+    #[prost(int64, tag = "4")]
+    #[builder(setter(into), default)]
+    pub min_duration_secs: i64,
+    /// search the active members of the group as well
+    #[prost(string, tag = "5")]
+    #[builder(setter(into), default)]
+    pub group_id: ::prost::alloc::string::String,
+}
+/// a free interval of a resource within the search window
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FreeSlot {
+    /// resource id for the free slot
+    #[prost(string, tag = "1")]
+    pub resource_id: ::prost::alloc::string::String,
+    /// start time of the free slot
+    #[prost(message, optional, tag = "2")]
+    pub start: ::core::option::Option<::prost_types::Timestamp>,
+    /// end time of the free slot
+    #[prost(message, optional, tag = "3")]
+    pub end: ::core::option::Option<::prost_types::Timestamp>,
+    /// how many more reservations the resource could take during the whole slot
+    #[prost(int32, tag = "4")]
+    pub remaining: i32,
+}
+/// Free slots are ordered by resource id and start time
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AvailabilityResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub slots: ::prost::alloc::vec::Vec<FreeSlot>,
+}
+/// time window of a reservation involved in a conflict
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ConflictWindow {
+    #[prost(string, tag = "1")]
+    pub resource_id: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "2")]
+    pub start: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(message, optional, tag = "3")]
+    pub end: ::core::option::Option<::prost_types::Timestamp>,
+}
+/// attached to the FAILED_PRECONDITION status when a reservation conflicts with an existing one,
+/// one detail for every conflict if several reservations are made at once
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ConflictDetail {
+    #[prost(enumeration = "ErrorCode", tag = "1")]
+    pub code: i32,
+    /// window of the reservation being made, empty if the conflict could not be parsed
+    #[prost(message, optional, tag = "2")]
+    pub new: ::core::option::Option<ConflictWindow>,
+    /// window of the existing reservation, empty if the conflict could not be parsed.
+    /// For ERROR_CODE_RESOURCE_FULL the interval in which the resource is full
+    #[prost(message, optional, tag = "3")]
+    pub old: ::core::option::Option<ConflictWindow>,
+    /// id of the existing reservation, 0 if unknown
+    #[prost(int64, tag = "4")]
+    pub conflicting_id: i64,
+    /// capacity of the resource for ERROR_CODE_RESOURCE_FULL
+    #[prost(int32, tag = "5")]
+    pub capacity: i32,
+    /// why the resource can't be reserved for ERROR_CODE_RESOURCE_UNAVAILABLE
+    #[prost(string, tag = "6")]
+    pub reason: ::prost::alloc::string::String,
+}
+/// wire compatible with google.rpc.Status, sent as the details of a failed gRPC status
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RpcStatus {
+    #[prost(int32, tag = "1")]
+    pub code: i32,
+    #[prost(string, tag = "2")]
+    pub message: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "3")]
+    pub details: ::prost::alloc::vec::Vec<::prost_types::Any>,
+}
+/// a bookable resource, reservations could only be made on active resources
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Resource {
+    /// unique id, used as the resource_id of reservations
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub name: ::prost::alloc::string::String,
+    /// free-form type of the resource, e.g. "room", "projector"
+    #[prost(string, tag = "3")]
+    pub resource_type: ::prost::alloc::string::String,
+    #[prost(map = "string, string", tag = "4")]
+    pub metadata:
+        ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::string::String>,
+    /// inactive resources are kept for their existing reservations but could not be reserved
+    #[prost(bool, tag = "5")]
+    pub active: bool,
+    /// how many reservations could overlap, e.g. the desks of a desk pool. 0 is the same as 1
+    #[prost(int32, tag = "6")]
+    pub capacity: i32,
+    /// group the resource belongs to, e.g. the floor of a room. Empty for top level resources.
+    /// The members of a group are the resources without children below it
+    #[prost(string, tag = "7")]
+    pub parent_id: ::prost::alloc::string::String,
+    /// IANA timezone of the opening hours, e.g. "Europe/Berlin". Empty is UTC
+    #[prost(string, tag = "8")]
+    pub timezone: ::prost::alloc::string::String,
+    /// reservations must be within the opening hours, if empty the resource is always open
+    #[prost(message, repeated, tag = "9")]
+    pub opening_hours: ::prost::alloc::vec::Vec<OpeningHours>,
+}
+/// weekly opening hours of a resource, local to the timezone of the resource
+#[derive(serde::Serialize, serde::Deserialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OpeningHours {
+    /// ISO weekday, 1 is Monday and 7 is Sunday
+    #[prost(int32, tag = "1")]
+    pub weekday: i32,
+    /// opening time in seconds after local midnight
+    #[prost(int32, tag = "2")]
+    pub opens_secs: i32,
+    /// closing time in seconds after local midnight, at most 86400. Hours closing at midnight
+    /// followed by hours opening at midnight of the next day are open across midnight
+    #[prost(int32, tag = "3")]
+    pub closes_secs: i32,
+}
+/// a period in which a resource could not be reserved, e.g. for maintenance
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Blackout {
+    #[prost(int64, tag = "1")]
+    pub id: i64,
+    #[prost(string, tag = "2")]
+    pub resource_id: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "3")]
+    pub start: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(message, optional, tag = "4")]
+    pub end: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(string, tag = "5")]
+    pub reason: ::prost::alloc::string::String,
+}
+/// To add a resource to the catalog, send a CreateResourceRequest
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateResourceRequest {
+    #[prost(message, optional, tag = "1")]
+    pub resource: ::core::option::Option<Resource>,
+}
+/// Created resource will be returned in CreateResourceResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateResourceResponse {
+    #[prost(message, optional, tag = "1")]
+    pub resource: ::core::option::Option<Resource>,
+}
+/// To get a resource, send a GetResourceRequest
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetResourceRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+}
+/// Get resource will be returned in GetResourceResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetResourceResponse {
+    #[prost(message, optional, tag = "1")]
+    pub resource: ::core::option::Option<Resource>,
+}
+/// To update a resource, send an UpdateResourceRequest. Every field except id is replaced
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateResourceRequest {
+    #[prost(message, optional, tag = "1")]
+    pub resource: ::core::option::Option<Resource>,
+}
+/// Updated resource will be returned in UpdateResourceResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateResourceResponse {
+    #[prost(message, optional, tag = "1")]
+    pub resource: ::core::option::Option<Resource>,
+}
+/// To delete a resource, send a DeleteResourceRequest. Its reservations are kept and
+/// its children become top level resources
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeleteResourceRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+}
+/// Deleted resource will be returned in DeleteResourceResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeleteResourceResponse {
+    #[prost(message, optional, tag = "1")]
+    pub resource: ::core::option::Option<Resource>,
+}
+/// To list the resources in the catalog, send a ListResourcesRequest
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListResourcesRequest {
+    /// only list resources of this type, if empty list all types
+    #[prost(string, tag = "1")]
+    pub resource_type: ::prost::alloc::string::String,
+    /// if true inactive resources are listed as well
+    #[prost(bool, tag = "2")]
+    pub include_inactive: bool,
+    /// only list the direct children of this resource, if empty list resources at every level
+    #[prost(string, tag = "3")]
+    pub parent_id: ::prost::alloc::string::String,
+}
+/// Resources will be returned order by id
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListResourcesResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub resources: ::prost::alloc::vec::Vec<Resource>,
+}
+/// To add a blackout to a resource, send an AddBlackoutRequest (id should be empty).
+/// Existing reservations in the period are kept
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AddBlackoutRequest {
+    #[prost(message, optional, tag = "1")]
+    pub blackout: ::core::option::Option<Blackout>,
+}
+/// Added blackout will be returned in AddBlackoutResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AddBlackoutResponse {
+    #[prost(message, optional, tag = "1")]
+    pub blackout: ::core::option::Option<Blackout>,
+}
+/// To remove a blackout, send a RemoveBlackoutRequest
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RemoveBlackoutRequest {
+    #[prost(int64, tag = "1")]
+    pub id: i64,
+}
+/// Removed blackout will be returned in RemoveBlackoutResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RemoveBlackoutResponse {
+    #[prost(message, optional, tag = "1")]
+    pub blackout: ::core::option::Option<Blackout>,
+}
+/// To list the blackouts of a resource, send a ListBlackoutsRequest
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListBlackoutsRequest {
+    #[prost(string, tag = "1")]
+    pub resource_id: ::prost::alloc::string::String,
+}
+/// Blackouts will be returned order by start time
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListBlackoutsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub blackouts: ::prost::alloc::vec::Vec<Blackout>,
+}
+/// which occurrences of a recurring series an operation applies to
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum SeriesScope {
+    /// only the given occurrence
+    This = 0,
+    /// the given occurrence and every occurrence starting after it
+    ThisAndFollowing = 1,
+    /// every occurrence of the series
+    All = 2,
+}
+impl SeriesScope {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            SeriesScope::This => "SERIES_SCOPE_THIS",
+            SeriesScope::ThisAndFollowing => "SERIES_SCOPE_THIS_AND_FOLLOWING",
+            SeriesScope::All => "SERIES_SCOPE_ALL",
         }
-        pub fn with_interceptor<F>(
-            inner: T,
-            interceptor: F,
-        ) -> ReservationServiceClient<InterceptedService<T, F>>
-        where
-            F: tonic::service::Interceptor,
-            T::ResponseBody: Default,
-            T: tonic::codegen::Service<
-                http::Request<tonic::body::BoxBody>,
-                Response = http::Response<
-                    <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
-                >,
-            >,
-            <T as tonic::codegen::Service<http::Request<tonic::body::BoxBody>>>::Error:
-                Into<StdError> + Send + Sync,
-        {
-            ReservationServiceClient::new(InterceptedService::new(inner, interceptor))
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "SERIES_SCOPE_THIS" => Some(Self::This),
+            "SERIES_SCOPE_THIS_AND_FOLLOWING" => Some(Self::ThisAndFollowing),
+            "SERIES_SCOPE_ALL" => Some(Self::All),
+            _ => None,
         }
-        /// Compress requests with the given encoding.
-        ///
-        /// This requires the server to support it otherwise it might respond with an
-        /// error.
-        #[must_use]
-        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.inner = self.inner.send_compressed(encoding);
-            self
+    }
+}
+/// reservation status for a given time period
+#[derive(
+    sqlx::Type, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration,
+)]
+#[repr(i32)]
+pub enum ReservationStatus {
+    Unknown = 0,
+    Pending = 1,
+    Confirmed = 2,
+    Blocked = 3,
+    Cancelled = 4,
+}
+impl ReservationStatus {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ReservationStatus::Unknown => "RESERVATION_STATUS_UNKNOWN",
+            ReservationStatus::Pending => "RESERVATION_STATUS_PENDING",
+            ReservationStatus::Confirmed => "RESERVATION_STATUS_CONFIRMED",
+            ReservationStatus::Blocked => "RESERVATION_STATUS_BLOCKED",
+            ReservationStatus::Cancelled => "RESERVATION_STATUS_CANCELLED",
         }
-        /// Enable decompressing responses.
-        #[must_use]
-        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.inner = self.inner.accept_compressed(encoding);
-            self
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "RESERVATION_STATUS_UNKNOWN" => Some(Self::Unknown),
+            "RESERVATION_STATUS_PENDING" => Some(Self::Pending),
+            "RESERVATION_STATUS_CONFIRMED" => Some(Self::Confirmed),
+            "RESERVATION_STATUS_BLOCKED" => Some(Self::Blocked),
+            "RESERVATION_STATUS_CANCELLED" => Some(Self::Cancelled),
+            _ => None,
         }
-        /// make a reservation
-        pub async fn reserve(
-            &mut self,
-            request: impl tonic::IntoRequest<super::ReserveRequest>,
-        ) -> Result<tonic::Response<super::ReserveResponse>, tonic::Status> {
-            self.inner.ready().await.map_err(|e| {
-                tonic::Status::new(
-                    tonic::Code::Unknown,
-                    format!("Service was not ready: {}", e.into()),
-                )
-            })?;
-            let codec = tonic::codec::ProstCodec::default();
-            let path =
-                http::uri::PathAndQuery::from_static("/reservation.ReservationService/reserve");
-            self.inner.unary(request.into_request(), path, codec).await
+    }
+}
+/// when reservation is updated, record the update type
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ReservationUpdateType {
+    Unknown = 0,
+    Create = 1,
+    Update = 2,
+    Delete = 3,
+}
+impl ReservationUpdateType {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ReservationUpdateType::Unknown => "RESERVATION_UPDATE_TYPE_UNKNOWN",
+            ReservationUpdateType::Create => "RESERVATION_UPDATE_TYPE_CREATE",
+            ReservationUpdateType::Update => "RESERVATION_UPDATE_TYPE_UPDATE",
+            ReservationUpdateType::Delete => "RESERVATION_UPDATE_TYPE_DELETE",
         }
-        /// confirm a pending reservation, if reservation is not pending, do nothing
-        pub async fn confirm(
-            &mut self,
-            request: impl tonic::IntoRequest<super::ConfirmRequest>,
-        ) -> Result<tonic::Response<super::ConfirmResponse>, tonic::Status> {
-            self.inner.ready().await.map_err(|e| {
-                tonic::Status::new(
-                    tonic::Code::Unknown,
-                    format!("Service was not ready: {}", e.into()),
-                )
-            })?;
-            let codec = tonic::codec::ProstCodec::default();
-            let path =
-                http::uri::PathAndQuery::from_static("/reservation.ReservationService/confirm");
-            self.inner.unary(request.into_request(), path, codec).await
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "RESERVATION_UPDATE_TYPE_UNKNOWN" => Some(Self::Unknown),
+            "RESERVATION_UPDATE_TYPE_CREATE" => Some(Self::Create),
+            "RESERVATION_UPDATE_TYPE_UPDATE" => Some(Self::Update),
+            "RESERVATION_UPDATE_TYPE_DELETE" => Some(Self::Delete),
+            _ => None,
         }
-        /// update the reservation note
-        pub async fn update(
-            &mut self,
-            request: impl tonic::IntoRequest<super::UpdateRequest>,
-        ) -> Result<tonic::Response<super::UpdateResponse>, tonic::Status> {
-            self.inner.ready().await.map_err(|e| {
-                tonic::Status::new(
+    }
+}
+/// how reservations are matched against the time window of a query
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ReservationQueryMode {
+    /// reservation is fully contained in the window
+    Contained = 0,
+    /// reservation overlaps with the window
+    Overlapping = 1,
+    /// reservation starts within the window
+    StartingWithin = 2,
+}
+impl ReservationQueryMode {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ReservationQueryMode::Contained => "RESERVATION_QUERY_MODE_CONTAINED",
+            ReservationQueryMode::Overlapping => "RESERVATION_QUERY_MODE_OVERLAPPING",
+            ReservationQueryMode::StartingWithin => "RESERVATION_QUERY_MODE_STARTING_WITHIN",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "RESERVATION_QUERY_MODE_CONTAINED" => Some(Self::Contained),
+            "RESERVATION_QUERY_MODE_OVERLAPPING" => Some(Self::Overlapping),
+            "RESERVATION_QUERY_MODE_STARTING_WITHIN" => Some(Self::StartingWithin),
+            _ => None,
+        }
+    }
+}
+/// how a recurring reservation handles occurrences that conflict
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum RecurrenceMode {
+    /// reserve nothing if any occurrence conflicts
+    AllOrNothing = 0,
+    /// reserve every occurrence that doesn't conflict
+    BestEffort = 1,
+}
+impl RecurrenceMode {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            RecurrenceMode::AllOrNothing => "RECURRENCE_MODE_ALL_OR_NOTHING",
+            RecurrenceMode::BestEffort => "RECURRENCE_MODE_BEST_EFFORT",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "RECURRENCE_MODE_ALL_OR_NOTHING" => Some(Self::AllOrNothing),
+            "RECURRENCE_MODE_BEST_EFFORT" => Some(Self::BestEffort),
+            _ => None,
+        }
+    }
+}
+/// stable error codes carried in the details of a failed gRPC status
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ErrorCode {
+    Unknown = 0,
+    ReservationConflict = 1,
+    /// the resource has no capacity left, old is the interval in which it is full
+    ResourceFull = 2,
+    /// the resource is closed or blacked out in the new window, reason tells which
+    ResourceUnavailable = 3,
+}
+impl ErrorCode {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ErrorCode::Unknown => "ERROR_CODE_UNKNOWN",
+            ErrorCode::ReservationConflict => "ERROR_CODE_RESERVATION_CONFLICT",
+            ErrorCode::ResourceFull => "ERROR_CODE_RESOURCE_FULL",
+            ErrorCode::ResourceUnavailable => "ERROR_CODE_RESOURCE_UNAVAILABLE",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "ERROR_CODE_UNKNOWN" => Some(Self::Unknown),
+            "ERROR_CODE_RESERVATION_CONFLICT" => Some(Self::ReservationConflict),
+            "ERROR_CODE_RESOURCE_FULL" => Some(Self::ResourceFull),
+            "ERROR_CODE_RESOURCE_UNAVAILABLE" => Some(Self::ResourceUnavailable),
+            _ => None,
+        }
+    }
+}
+/// Generated client implementations.
+pub mod reservation_service_client {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::http::Uri;
+    use tonic::codegen::*;
+    /// Reservation service
+    #[derive(Debug, Clone)]
+    pub struct ReservationServiceClient<T> {
+        inner: tonic::client::Grpc<T>,
+    }
+    impl ReservationServiceClient<tonic::transport::Channel> {
+        /// Attempt to create a new client by connecting to a given endpoint.
+        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
+        where
+            D: std::convert::TryInto<tonic::transport::Endpoint>,
+            D::Error: Into<StdError>,
+        {
+            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
+            Ok(Self::new(conn))
+        }
+    }
+    impl<T> ReservationServiceClient<T>
+    where
+        T: tonic::client::GrpcService<tonic::body::BoxBody>,
+        T::Error: Into<StdError>,
+        T::ResponseBody: Body<Data = Bytes> + Send + 'static,
+        <T::ResponseBody as Body>::Error: Into<StdError> + Send,
+    {
+        pub fn new(inner: T) -> Self {
+            let inner = tonic::client::Grpc::new(inner);
+            Self { inner }
+        }
+        pub fn with_origin(inner: T, origin: Uri) -> Self {
+            let inner = tonic::client::Grpc::with_origin(inner, origin);
+            Self { inner }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> ReservationServiceClient<InterceptedService<T, F>>
+        where
+            F: tonic::service::Interceptor,
+            T::ResponseBody: Default,
+            T: tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+                Response = http::Response<
+                    <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
+                >,
+            >,
+            <T as tonic::codegen::Service<http::Request<tonic::body::BoxBody>>>::Error:
+                Into<StdError> + Send + Sync,
+        {
+            ReservationServiceClient::new(InterceptedService::new(inner, interceptor))
+        }
+        /// Compress requests with the given encoding.
+        ///
+        /// This requires the server to support it otherwise it might respond with an
+        /// error.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.send_compressed(encoding);
+            self
+        }
+        /// Enable decompressing responses.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.accept_compressed(encoding);
+            self
+        }
+        /// make a reservation
+        pub async fn reserve(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ReserveRequest>,
+        ) -> Result<tonic::Response<super::ReserveResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
                     tonic::Code::Unknown,
                     format!("Service was not ready: {}", e.into()),
                 )
             })?;
             let codec = tonic::codec::ProstCodec::default();
             let path =
-                http::uri::PathAndQuery::from_static("/reservation.ReservationService/update");
+                http::uri::PathAndQuery::from_static("/reservation.ReservationService/reserve");
             self.inner.unary(request.into_request(), path, codec).await
         }
-        /// cancel a reservation
-        pub async fn cancel(
+        /// reserve the first free of several resources, fails with every conflict if none is free
+        pub async fn reserve_any(
             &mut self,
-            request: impl tonic::IntoRequest<super::CancelRequest>,
-        ) -> Result<tonic::Response<super::CancelResponse>, tonic::Status> {
+            request: impl tonic::IntoRequest<super::ReserveAnyRequest>,
+        ) -> Result<tonic::Response<super::ReserveAnyResponse>, tonic::Status> {
             self.inner.ready().await.map_err(|e| {
                 tonic::Status::new(
                     tonic::Code::Unknown,
@@ -385,14 +1191,14 @@ pub mod reservation_service_client {
             })?;
             let codec = tonic::codec::ProstCodec::default();
             let path =
-                http::uri::PathAndQuery::from_static("/reservation.ReservationService/cancel");
+                http::uri::PathAndQuery::from_static("/reservation.ReservationService/reserve_any");
             self.inner.unary(request.into_request(), path, codec).await
         }
-        /// get a reservation by id
-        pub async fn get(
+        /// make several reservations in one transaction, fails with every conflict if any of them conflicts
+        pub async fn reserve_many(
             &mut self,
-            request: impl tonic::IntoRequest<super::GetRequest>,
-        ) -> Result<tonic::Response<super::GetResponse>, tonic::Status> {
+            request: impl tonic::IntoRequest<super::ReserveManyRequest>,
+        ) -> Result<tonic::Response<super::ReserveManyResponse>, tonic::Status> {
             self.inner.ready().await.map_err(|e| {
                 tonic::Status::new(
                     tonic::Code::Unknown,
@@ -400,15 +1206,16 @@ pub mod reservation_service_client {
                 )
             })?;
             let codec = tonic::codec::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static("/reservation.ReservationService/get");
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/reserve_many",
+            );
             self.inner.unary(request.into_request(), path, codec).await
         }
-        /// query reservations by resource id, user id, status, start time, end time
-        pub async fn query(
+        /// make a recurring reservation, occurrences are expanded from the RRULE
+        pub async fn reserve_recurring(
             &mut self,
-            request: impl tonic::IntoRequest<super::QueryRequest>,
-        ) -> Result<tonic::Response<tonic::codec::Streaming<super::Reservation>>, tonic::Status>
-        {
+            request: impl tonic::IntoRequest<super::ReserveRecurringRequest>,
+        ) -> Result<tonic::Response<super::ReserveRecurringResponse>, tonic::Status> {
             self.inner.ready().await.map_err(|e| {
                 tonic::Status::new(
                     tonic::Code::Unknown,
@@ -416,17 +1223,16 @@ pub mod reservation_service_client {
                 )
             })?;
             let codec = tonic::codec::ProstCodec::default();
-            let path =
-                http::uri::PathAndQuery::from_static("/reservation.ReservationService/query");
-            self.inner
-                .server_streaming(request.into_request(), path, codec)
-                .await
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/reserve_recurring",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
         }
-        /// filter reservations, order by reservatioin id
-        pub async fn filter(
+        /// check a reservation like reserve does and return the conflicting reservations, nothing is inserted
+        pub async fn check(
             &mut self,
-            request: impl tonic::IntoRequest<super::FilterRequest>,
-        ) -> Result<tonic::Response<super::FilterResponse>, tonic::Status> {
+            request: impl tonic::IntoRequest<super::CheckRequest>,
+        ) -> Result<tonic::Response<super::CheckResponse>, tonic::Status> {
             self.inner.ready().await.map_err(|e| {
                 tonic::Status::new(
                     tonic::Code::Unknown,
@@ -435,15 +1241,14 @@ pub mod reservation_service_client {
             })?;
             let codec = tonic::codec::ProstCodec::default();
             let path =
-                http::uri::PathAndQuery::from_static("/reservation.ReservationService/filter");
+                http::uri::PathAndQuery::from_static("/reservation.ReservationService/check");
             self.inner.unary(request.into_request(), path, codec).await
         }
-        /// another system could monitor newly added/confirmed/cancelled reservations
-        pub async fn listen(
+        /// confirm a pending reservation, fails if reservation is not pending
+        pub async fn confirm(
             &mut self,
-            request: impl tonic::IntoRequest<super::ListenRequest>,
-        ) -> Result<tonic::Response<tonic::codec::Streaming<super::Reservation>>, tonic::Status>
-        {
+            request: impl tonic::IntoRequest<super::ConfirmRequest>,
+        ) -> Result<tonic::Response<super::ConfirmResponse>, tonic::Status> {
             self.inner.ready().await.map_err(|e| {
                 tonic::Status::new(
                     tonic::Code::Unknown,
@@ -452,135 +1257,1259 @@ pub mod reservation_service_client {
             })?;
             let codec = tonic::codec::ProstCodec::default();
             let path =
-                http::uri::PathAndQuery::from_static("/reservation.ReservationService/listen");
-            self.inner
-                .server_streaming(request.into_request(), path, codec)
-                .await
+                http::uri::PathAndQuery::from_static("/reservation.ReservationService/confirm");
+            self.inner.unary(request.into_request(), path, codec).await
         }
-    }
-}
-/// Generated server implementations.
-pub mod reservation_service_server {
-    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
-    use tonic::codegen::*;
-    /// Generated trait containing gRPC methods that should be implemented for use with ReservationServiceServer.
-    #[async_trait]
-    pub trait ReservationService: Send + Sync + 'static {
-        /// make a reservation
-        async fn reserve(
-            &self,
-            request: tonic::Request<super::ReserveRequest>,
-        ) -> Result<tonic::Response<super::ReserveResponse>, tonic::Status>;
-        /// confirm a pending reservation, if reservation is not pending, do nothing
-        async fn confirm(
-            &self,
-            request: tonic::Request<super::ConfirmRequest>,
-        ) -> Result<tonic::Response<super::ConfirmResponse>, tonic::Status>;
-        /// update the reservation note
-        async fn update(
-            &self,
-            request: tonic::Request<super::UpdateRequest>,
-        ) -> Result<tonic::Response<super::UpdateResponse>, tonic::Status>;
-        /// cancel a reservation
-        async fn cancel(
-            &self,
-            request: tonic::Request<super::CancelRequest>,
-        ) -> Result<tonic::Response<super::CancelResponse>, tonic::Status>;
-        /// get a reservation by id
-        async fn get(
-            &self,
-            request: tonic::Request<super::GetRequest>,
-        ) -> Result<tonic::Response<super::GetResponse>, tonic::Status>;
-        /// Server streaming response type for the query method.
-        type queryStream: futures_core::Stream<Item = Result<super::Reservation, tonic::Status>>
-            + Send
-            + 'static;
-        /// query reservations by resource id, user id, status, start time, end time
-        async fn query(
-            &self,
-            request: tonic::Request<super::QueryRequest>,
-        ) -> Result<tonic::Response<Self::queryStream>, tonic::Status>;
-        /// filter reservations, order by reservatioin id
-        async fn filter(
-            &self,
-            request: tonic::Request<super::FilterRequest>,
-        ) -> Result<tonic::Response<super::FilterResponse>, tonic::Status>;
-        /// Server streaming response type for the listen method.
-        type listenStream: futures_core::Stream<Item = Result<super::Reservation, tonic::Status>>
-            + Send
-            + 'static;
-        /// another system could monitor newly added/confirmed/cancelled reservations
-        async fn listen(
-            &self,
-            request: tonic::Request<super::ListenRequest>,
-        ) -> Result<tonic::Response<Self::listenStream>, tonic::Status>;
-    }
-    /// Reservation service
-    #[derive(Debug)]
-    pub struct ReservationServiceServer<T: ReservationService> {
-        inner: _Inner<T>,
-        accept_compression_encodings: EnabledCompressionEncodings,
-        send_compression_encodings: EnabledCompressionEncodings,
-    }
-    struct _Inner<T>(Arc<T>);
-    impl<T: ReservationService> ReservationServiceServer<T> {
-        pub fn new(inner: T) -> Self {
-            Self::from_arc(Arc::new(inner))
+        /// move a reservation to another status following the allowed transitions:
+        /// pending -> confirmed, confirmed -> pending, pending -> blocked, blocked -> pending,
+        /// pending/confirmed/blocked -> cancelled
+        pub async fn transition(
+            &mut self,
+            request: impl tonic::IntoRequest<super::TransitionRequest>,
+        ) -> Result<tonic::Response<super::TransitionResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/reservation.ReservationService/transition");
+            self.inner.unary(request.into_request(), path, codec).await
         }
-        pub fn from_arc(inner: Arc<T>) -> Self {
-            let inner = _Inner(inner);
-            Self {
-                inner,
-                accept_compression_encodings: Default::default(),
-                send_compression_encodings: Default::default(),
-            }
+        /// confirm several pending reservations, every id gets its own result
+        pub async fn confirm_many(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ConfirmManyRequest>,
+        ) -> Result<tonic::Response<super::ConfirmManyResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/confirm_many",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
         }
-        pub fn with_interceptor<F>(inner: T, interceptor: F) -> InterceptedService<Self, F>
-        where
-            F: tonic::service::Interceptor,
+        /// update the reservation note
+        pub async fn update(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UpdateRequest>,
+        ) -> Result<tonic::Response<super::UpdateResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/reservation.ReservationService/update");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// move the reservation to another time window, fails if the new window conflicts
+        pub async fn reschedule(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RescheduleRequest>,
+        ) -> Result<tonic::Response<super::RescheduleResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/reservation.ReservationService/reschedule");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// cancel a reservation, it is kept with cancelled status and no longer blocks its time window
+        pub async fn cancel(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CancelRequest>,
+        ) -> Result<tonic::Response<super::CancelResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/reservation.ReservationService/cancel");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// cancel several reservations, every id gets its own result
+        pub async fn cancel_many(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CancelManyRequest>,
+        ) -> Result<tonic::Response<super::CancelManyResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/reservation.ReservationService/cancel_many");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// update the note of the selected occurrences of a recurring series
+        pub async fn update_series(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UpdateSeriesRequest>,
+        ) -> Result<tonic::Response<super::UpdateSeriesResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/update_series",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// cancel the selected occurrences of a recurring series
+        pub async fn cancel_series(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CancelSeriesRequest>,
+        ) -> Result<tonic::Response<super::CancelSeriesResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/cancel_series",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// move the selected occurrences of a recurring series by a fixed offset
+        pub async fn shift_series(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ShiftSeriesRequest>,
+        ) -> Result<tonic::Response<super::ShiftSeriesResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/shift_series",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// get a reservation by id
+        pub async fn get(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetRequest>,
+        ) -> Result<tonic::Response<super::GetResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/reservation.ReservationService/get");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// get several reservations by id, every id gets its own result
+        pub async fn get_many(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetManyRequest>,
+        ) -> Result<tonic::Response<super::GetManyResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/reservation.ReservationService/get_many");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// query reservations by resource id, user id, status, start time, end time
+        pub async fn query(
+            &mut self,
+            request: impl tonic::IntoRequest<super::QueryRequest>,
+        ) -> Result<tonic::Response<tonic::codec::Streaming<super::Reservation>>, tonic::Status>
         {
-            InterceptedService::new(Self::new(inner), interceptor)
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/reservation.ReservationService/query");
+            self.inner
+                .server_streaming(request.into_request(), path, codec)
+                .await
         }
-        /// Enable decompressing requests with the given encoding.
-        #[must_use]
-        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.accept_compression_encodings.enable(encoding);
-            self
+        /// filter reservations, order by reservatioin id
+        pub async fn filter(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FilterRequest>,
+        ) -> Result<tonic::Response<super::FilterResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/reservation.ReservationService/filter");
+            self.inner.unary(request.into_request(), path, codec).await
         }
-        /// Compress responses with the given encoding, if the client supports it.
-        #[must_use]
-        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.send_compression_encodings.enable(encoding);
-            self
+        /// another system could monitor newly added/confirmed/cancelled reservations
+        pub async fn listen(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListenRequest>,
+        ) -> Result<tonic::Response<tonic::codec::Streaming<super::ListenResponse>>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/reservation.ReservationService/listen");
+            self.inner
+                .server_streaming(request.into_request(), path, codec)
+                .await
         }
-    }
-    impl<T, B> tonic::codegen::Service<http::Request<B>> for ReservationServiceServer<T>
-    where
-        T: ReservationService,
-        B: Body + Send + 'static,
-        B::Error: Into<StdError> + Send + 'static,
-    {
-        type Response = http::Response<tonic::body::BoxBody>;
-        type Error = std::convert::Infallible;
-        type Future = BoxFuture<Self::Response, Self::Error>;
-        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
-            Poll::Ready(Ok(()))
+        /// acknowledge the changes a consumer has received, a resumed listen starts after them
+        pub async fn ack(
+            &mut self,
+            request: impl tonic::IntoRequest<super::AckRequest>,
+        ) -> Result<tonic::Response<super::AckResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/reservation.ReservationService/ack");
+            self.inner.unary(request.into_request(), path, codec).await
         }
-        fn call(&mut self, req: http::Request<B>) -> Self::Future {
-            let inner = self.inner.clone();
-            match req.uri().path() {
-                "/reservation.ReservationService/reserve" => {
+        /// get the change history of a reservation, including deleted ones
+        pub async fn history(
+            &mut self,
+            request: impl tonic::IntoRequest<super::HistoryRequest>,
+        ) -> Result<tonic::Response<super::HistoryResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/reservation.ReservationService/history");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// find free slots of resources within a time window
+        pub async fn availability(
+            &mut self,
+            request: impl tonic::IntoRequest<super::AvailabilityRequest>,
+        ) -> Result<tonic::Response<super::AvailabilityResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/availability",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// add a resource to the catalog
+        pub async fn create_resource(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CreateResourceRequest>,
+        ) -> Result<tonic::Response<super::CreateResourceResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/create_resource",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// get a resource by id
+        pub async fn get_resource(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetResourceRequest>,
+        ) -> Result<tonic::Response<super::GetResourceResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/get_resource",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// update a resource, deactivate it to stop new reservations on it
+        pub async fn update_resource(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UpdateResourceRequest>,
+        ) -> Result<tonic::Response<super::UpdateResourceResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/update_resource",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// delete a resource from the catalog
+        pub async fn delete_resource(
+            &mut self,
+            request: impl tonic::IntoRequest<super::DeleteResourceRequest>,
+        ) -> Result<tonic::Response<super::DeleteResourceResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/delete_resource",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// list the resources in the catalog
+        pub async fn list_resources(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListResourcesRequest>,
+        ) -> Result<tonic::Response<super::ListResourcesResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/list_resources",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// add a blackout period to a resource
+        pub async fn add_blackout(
+            &mut self,
+            request: impl tonic::IntoRequest<super::AddBlackoutRequest>,
+        ) -> Result<tonic::Response<super::AddBlackoutResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/add_blackout",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// remove a blackout period
+        pub async fn remove_blackout(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RemoveBlackoutRequest>,
+        ) -> Result<tonic::Response<super::RemoveBlackoutResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/remove_blackout",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        /// list the blackout periods of a resource
+        pub async fn list_blackouts(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListBlackoutsRequest>,
+        ) -> Result<tonic::Response<super::ListBlackoutsResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/list_blackouts",
+            );
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+    }
+}
+/// Generated server implementations.
+pub mod reservation_service_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with ReservationServiceServer.
+    #[async_trait]
+    pub trait ReservationService: Send + Sync + 'static {
+        /// make a reservation
+        async fn reserve(
+            &self,
+            request: tonic::Request<super::ReserveRequest>,
+        ) -> Result<tonic::Response<super::ReserveResponse>, tonic::Status>;
+        /// reserve the first free of several resources, fails with every conflict if none is free
+        async fn reserve_any(
+            &self,
+            request: tonic::Request<super::ReserveAnyRequest>,
+        ) -> Result<tonic::Response<super::ReserveAnyResponse>, tonic::Status>;
+        /// make several reservations in one transaction, fails with every conflict if any of them conflicts
+        async fn reserve_many(
+            &self,
+            request: tonic::Request<super::ReserveManyRequest>,
+        ) -> Result<tonic::Response<super::ReserveManyResponse>, tonic::Status>;
+        /// make a recurring reservation, occurrences are expanded from the RRULE
+        async fn reserve_recurring(
+            &self,
+            request: tonic::Request<super::ReserveRecurringRequest>,
+        ) -> Result<tonic::Response<super::ReserveRecurringResponse>, tonic::Status>;
+        /// check a reservation like reserve does and return the conflicting reservations, nothing is inserted
+        async fn check(
+            &self,
+            request: tonic::Request<super::CheckRequest>,
+        ) -> Result<tonic::Response<super::CheckResponse>, tonic::Status>;
+        /// confirm a pending reservation, fails if reservation is not pending
+        async fn confirm(
+            &self,
+            request: tonic::Request<super::ConfirmRequest>,
+        ) -> Result<tonic::Response<super::ConfirmResponse>, tonic::Status>;
+        /// move a reservation to another status following the allowed transitions:
+        /// pending -> confirmed, confirmed -> pending, pending -> blocked, blocked -> pending,
+        /// pending/confirmed/blocked -> cancelled
+        async fn transition(
+            &self,
+            request: tonic::Request<super::TransitionRequest>,
+        ) -> Result<tonic::Response<super::TransitionResponse>, tonic::Status>;
+        /// confirm several pending reservations, every id gets its own result
+        async fn confirm_many(
+            &self,
+            request: tonic::Request<super::ConfirmManyRequest>,
+        ) -> Result<tonic::Response<super::ConfirmManyResponse>, tonic::Status>;
+        /// update the reservation note
+        async fn update(
+            &self,
+            request: tonic::Request<super::UpdateRequest>,
+        ) -> Result<tonic::Response<super::UpdateResponse>, tonic::Status>;
+        /// move the reservation to another time window, fails if the new window conflicts
+        async fn reschedule(
+            &self,
+            request: tonic::Request<super::RescheduleRequest>,
+        ) -> Result<tonic::Response<super::RescheduleResponse>, tonic::Status>;
+        /// cancel a reservation, it is kept with cancelled status and no longer blocks its time window
+        async fn cancel(
+            &self,
+            request: tonic::Request<super::CancelRequest>,
+        ) -> Result<tonic::Response<super::CancelResponse>, tonic::Status>;
+        /// cancel several reservations, every id gets its own result
+        async fn cancel_many(
+            &self,
+            request: tonic::Request<super::CancelManyRequest>,
+        ) -> Result<tonic::Response<super::CancelManyResponse>, tonic::Status>;
+        /// update the note of the selected occurrences of a recurring series
+        async fn update_series(
+            &self,
+            request: tonic::Request<super::UpdateSeriesRequest>,
+        ) -> Result<tonic::Response<super::UpdateSeriesResponse>, tonic::Status>;
+        /// cancel the selected occurrences of a recurring series
+        async fn cancel_series(
+            &self,
+            request: tonic::Request<super::CancelSeriesRequest>,
+        ) -> Result<tonic::Response<super::CancelSeriesResponse>, tonic::Status>;
+        /// move the selected occurrences of a recurring series by a fixed offset
+        async fn shift_series(
+            &self,
+            request: tonic::Request<super::ShiftSeriesRequest>,
+        ) -> Result<tonic::Response<super::ShiftSeriesResponse>, tonic::Status>;
+        /// get a reservation by id
+        async fn get(
+            &self,
+            request: tonic::Request<super::GetRequest>,
+        ) -> Result<tonic::Response<super::GetResponse>, tonic::Status>;
+        /// get several reservations by id, every id gets its own result
+        async fn get_many(
+            &self,
+            request: tonic::Request<super::GetManyRequest>,
+        ) -> Result<tonic::Response<super::GetManyResponse>, tonic::Status>;
+        /// Server streaming response type for the query method.
+        type queryStream: futures_core::Stream<Item = Result<super::Reservation, tonic::Status>>
+            + Send
+            + 'static;
+        /// query reservations by resource id, user id, status, start time, end time
+        async fn query(
+            &self,
+            request: tonic::Request<super::QueryRequest>,
+        ) -> Result<tonic::Response<Self::queryStream>, tonic::Status>;
+        /// filter reservations, order by reservatioin id
+        async fn filter(
+            &self,
+            request: tonic::Request<super::FilterRequest>,
+        ) -> Result<tonic::Response<super::FilterResponse>, tonic::Status>;
+        /// Server streaming response type for the listen method.
+        type listenStream: futures_core::Stream<Item = Result<super::ListenResponse, tonic::Status>>
+            + Send
+            + 'static;
+        /// another system could monitor newly added/confirmed/cancelled reservations
+        async fn listen(
+            &self,
+            request: tonic::Request<super::ListenRequest>,
+        ) -> Result<tonic::Response<Self::listenStream>, tonic::Status>;
+        /// acknowledge the changes a consumer has received, a resumed listen starts after them
+        async fn ack(
+            &self,
+            request: tonic::Request<super::AckRequest>,
+        ) -> Result<tonic::Response<super::AckResponse>, tonic::Status>;
+        /// get the change history of a reservation, including deleted ones
+        async fn history(
+            &self,
+            request: tonic::Request<super::HistoryRequest>,
+        ) -> Result<tonic::Response<super::HistoryResponse>, tonic::Status>;
+        /// find free slots of resources within a time window
+        async fn availability(
+            &self,
+            request: tonic::Request<super::AvailabilityRequest>,
+        ) -> Result<tonic::Response<super::AvailabilityResponse>, tonic::Status>;
+        /// add a resource to the catalog
+        async fn create_resource(
+            &self,
+            request: tonic::Request<super::CreateResourceRequest>,
+        ) -> Result<tonic::Response<super::CreateResourceResponse>, tonic::Status>;
+        /// get a resource by id
+        async fn get_resource(
+            &self,
+            request: tonic::Request<super::GetResourceRequest>,
+        ) -> Result<tonic::Response<super::GetResourceResponse>, tonic::Status>;
+        /// update a resource, deactivate it to stop new reservations on it
+        async fn update_resource(
+            &self,
+            request: tonic::Request<super::UpdateResourceRequest>,
+        ) -> Result<tonic::Response<super::UpdateResourceResponse>, tonic::Status>;
+        /// delete a resource from the catalog
+        async fn delete_resource(
+            &self,
+            request: tonic::Request<super::DeleteResourceRequest>,
+        ) -> Result<tonic::Response<super::DeleteResourceResponse>, tonic::Status>;
+        /// list the resources in the catalog
+        async fn list_resources(
+            &self,
+            request: tonic::Request<super::ListResourcesRequest>,
+        ) -> Result<tonic::Response<super::ListResourcesResponse>, tonic::Status>;
+        /// add a blackout period to a resource
+        async fn add_blackout(
+            &self,
+            request: tonic::Request<super::AddBlackoutRequest>,
+        ) -> Result<tonic::Response<super::AddBlackoutResponse>, tonic::Status>;
+        /// remove a blackout period
+        async fn remove_blackout(
+            &self,
+            request: tonic::Request<super::RemoveBlackoutRequest>,
+        ) -> Result<tonic::Response<super::RemoveBlackoutResponse>, tonic::Status>;
+        /// list the blackout periods of a resource
+        async fn list_blackouts(
+            &self,
+            request: tonic::Request<super::ListBlackoutsRequest>,
+        ) -> Result<tonic::Response<super::ListBlackoutsResponse>, tonic::Status>;
+    }
+    /// Reservation service
+    #[derive(Debug)]
+    pub struct ReservationServiceServer<T: ReservationService> {
+        inner: _Inner<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+    }
+    struct _Inner<T>(Arc<T>);
+    impl<T: ReservationService> ReservationServiceServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            let inner = _Inner(inner);
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+            }
+        }
+        pub fn with_interceptor<F>(inner: T, interceptor: F) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for ReservationServiceServer<T>
+    where
+        T: ReservationService,
+        B: Body + Send + 'static,
+        B::Error: Into<StdError> + Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            let inner = self.inner.clone();
+            match req.uri().path() {
+                "/reservation.ReservationService/reserve" => {
+                    #[allow(non_camel_case_types)]
+                    struct reserveSvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService> tonic::server::UnaryService<super::ReserveRequest> for reserveSvc<T> {
+                        type Response = super::ReserveResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ReserveRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).reserve(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = reserveSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/reserve_any" => {
+                    #[allow(non_camel_case_types)]
+                    struct reserve_anySvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService>
+                        tonic::server::UnaryService<super::ReserveAnyRequest>
+                        for reserve_anySvc<T>
+                    {
+                        type Response = super::ReserveAnyResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ReserveAnyRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).reserve_any(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = reserve_anySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/reserve_many" => {
+                    #[allow(non_camel_case_types)]
+                    struct reserve_manySvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService>
+                        tonic::server::UnaryService<super::ReserveManyRequest>
+                        for reserve_manySvc<T>
+                    {
+                        type Response = super::ReserveManyResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ReserveManyRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).reserve_many(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = reserve_manySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/reserve_recurring" => {
+                    #[allow(non_camel_case_types)]
+                    struct reserve_recurringSvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService>
+                        tonic::server::UnaryService<super::ReserveRecurringRequest>
+                        for reserve_recurringSvc<T>
+                    {
+                        type Response = super::ReserveRecurringResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ReserveRecurringRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).reserve_recurring(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = reserve_recurringSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/check" => {
+                    #[allow(non_camel_case_types)]
+                    struct checkSvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService> tonic::server::UnaryService<super::CheckRequest> for checkSvc<T> {
+                        type Response = super::CheckResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CheckRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).check(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = checkSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/confirm" => {
+                    #[allow(non_camel_case_types)]
+                    struct confirmSvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService> tonic::server::UnaryService<super::ConfirmRequest> for confirmSvc<T> {
+                        type Response = super::ConfirmResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ConfirmRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).confirm(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = confirmSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/transition" => {
+                    #[allow(non_camel_case_types)]
+                    struct transitionSvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService>
+                        tonic::server::UnaryService<super::TransitionRequest> for transitionSvc<T>
+                    {
+                        type Response = super::TransitionResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::TransitionRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).transition(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = transitionSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/confirm_many" => {
+                    #[allow(non_camel_case_types)]
+                    struct confirm_manySvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService>
+                        tonic::server::UnaryService<super::ConfirmManyRequest>
+                        for confirm_manySvc<T>
+                    {
+                        type Response = super::ConfirmManyResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ConfirmManyRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).confirm_many(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = confirm_manySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/update" => {
+                    #[allow(non_camel_case_types)]
+                    struct updateSvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService> tonic::server::UnaryService<super::UpdateRequest> for updateSvc<T> {
+                        type Response = super::UpdateResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UpdateRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).update(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = updateSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/reschedule" => {
+                    #[allow(non_camel_case_types)]
+                    struct rescheduleSvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService>
+                        tonic::server::UnaryService<super::RescheduleRequest> for rescheduleSvc<T>
+                    {
+                        type Response = super::RescheduleResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RescheduleRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).reschedule(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = rescheduleSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/cancel" => {
+                    #[allow(non_camel_case_types)]
+                    struct cancelSvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService> tonic::server::UnaryService<super::CancelRequest> for cancelSvc<T> {
+                        type Response = super::CancelResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CancelRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).cancel(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = cancelSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/cancel_many" => {
+                    #[allow(non_camel_case_types)]
+                    struct cancel_manySvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService>
+                        tonic::server::UnaryService<super::CancelManyRequest>
+                        for cancel_manySvc<T>
+                    {
+                        type Response = super::CancelManyResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CancelManyRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).cancel_many(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = cancel_manySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/update_series" => {
+                    #[allow(non_camel_case_types)]
+                    struct update_seriesSvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService>
+                        tonic::server::UnaryService<super::UpdateSeriesRequest>
+                        for update_seriesSvc<T>
+                    {
+                        type Response = super::UpdateSeriesResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UpdateSeriesRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).update_series(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = update_seriesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/cancel_series" => {
+                    #[allow(non_camel_case_types)]
+                    struct cancel_seriesSvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService>
+                        tonic::server::UnaryService<super::CancelSeriesRequest>
+                        for cancel_seriesSvc<T>
+                    {
+                        type Response = super::CancelSeriesResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CancelSeriesRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).cancel_series(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = cancel_seriesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/shift_series" => {
+                    #[allow(non_camel_case_types)]
+                    struct shift_seriesSvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService>
+                        tonic::server::UnaryService<super::ShiftSeriesRequest>
+                        for shift_seriesSvc<T>
+                    {
+                        type Response = super::ShiftSeriesResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ShiftSeriesRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).shift_series(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = shift_seriesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/get" => {
+                    #[allow(non_camel_case_types)]
+                    struct getSvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService> tonic::server::UnaryService<super::GetRequest> for getSvc<T> {
+                        type Response = super::GetResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).get(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = getSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/get_many" => {
+                    #[allow(non_camel_case_types)]
+                    struct get_manySvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService> tonic::server::UnaryService<super::GetManyRequest> for get_manySvc<T> {
+                        type Response = super::GetManyResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetManyRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).get_many(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = get_manySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/query" => {
                     #[allow(non_camel_case_types)]
-                    struct reserveSvc<T: ReservationService>(pub Arc<T>);
-                    impl<T: ReservationService> tonic::server::UnaryService<super::ReserveRequest> for reserveSvc<T> {
-                        type Response = super::ReserveResponse;
+                    struct querySvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService>
+                        tonic::server::ServerStreamingService<super::QueryRequest> for querySvc<T>
+                    {
+                        type Response = super::Reservation;
+                        type ResponseStream = T::queryStream;
+                        type Future =
+                            BoxFuture<tonic::Response<Self::ResponseStream>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::QueryRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).query(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = querySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/filter" => {
+                    #[allow(non_camel_case_types)]
+                    struct filterSvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService> tonic::server::UnaryService<super::FilterRequest> for filterSvc<T> {
+                        type Response = super::FilterResponse;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::ReserveRequest>,
+                            request: tonic::Request<super::FilterRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).reserve(request).await };
+                            let fut = async move { (*inner).filter(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -589,7 +2518,7 @@ pub mod reservation_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = reserveSvc(inner);
+                        let method = filterSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
                             accept_compression_encodings,
@@ -600,18 +2529,54 @@ pub mod reservation_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/reservation.ReservationService/confirm" => {
+                "/reservation.ReservationService/listen" => {
                     #[allow(non_camel_case_types)]
-                    struct confirmSvc<T: ReservationService>(pub Arc<T>);
-                    impl<T: ReservationService> tonic::server::UnaryService<super::ConfirmRequest> for confirmSvc<T> {
-                        type Response = super::ConfirmResponse;
+                    struct listenSvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService>
+                        tonic::server::ServerStreamingService<super::ListenRequest>
+                        for listenSvc<T>
+                    {
+                        type Response = super::ListenResponse;
+                        type ResponseStream = T::listenStream;
+                        type Future =
+                            BoxFuture<tonic::Response<Self::ResponseStream>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListenRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).listen(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = listenSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/ack" => {
+                    #[allow(non_camel_case_types)]
+                    struct ackSvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService> tonic::server::UnaryService<super::AckRequest> for ackSvc<T> {
+                        type Response = super::AckResponse;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::ConfirmRequest>,
+                            request: tonic::Request<super::AckRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).confirm(request).await };
+                            let fut = async move { (*inner).ack(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -620,7 +2585,7 @@ pub mod reservation_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = confirmSvc(inner);
+                        let method = ackSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
                             accept_compression_encodings,
@@ -631,18 +2596,18 @@ pub mod reservation_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/reservation.ReservationService/update" => {
+                "/reservation.ReservationService/history" => {
                     #[allow(non_camel_case_types)]
-                    struct updateSvc<T: ReservationService>(pub Arc<T>);
-                    impl<T: ReservationService> tonic::server::UnaryService<super::UpdateRequest> for updateSvc<T> {
-                        type Response = super::UpdateResponse;
+                    struct historySvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService> tonic::server::UnaryService<super::HistoryRequest> for historySvc<T> {
+                        type Response = super::HistoryResponse;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::UpdateRequest>,
+                            request: tonic::Request<super::HistoryRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).update(request).await };
+                            let fut = async move { (*inner).history(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -651,7 +2616,7 @@ pub mod reservation_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = updateSvc(inner);
+                        let method = historySvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
                             accept_compression_encodings,
@@ -662,18 +2627,21 @@ pub mod reservation_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/reservation.ReservationService/cancel" => {
+                "/reservation.ReservationService/availability" => {
                     #[allow(non_camel_case_types)]
-                    struct cancelSvc<T: ReservationService>(pub Arc<T>);
-                    impl<T: ReservationService> tonic::server::UnaryService<super::CancelRequest> for cancelSvc<T> {
-                        type Response = super::CancelResponse;
+                    struct availabilitySvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService>
+                        tonic::server::UnaryService<super::AvailabilityRequest>
+                        for availabilitySvc<T>
+                    {
+                        type Response = super::AvailabilityResponse;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::CancelRequest>,
+                            request: tonic::Request<super::AvailabilityRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).cancel(request).await };
+                            let fut = async move { (*inner).availability(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -682,7 +2650,7 @@ pub mod reservation_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = cancelSvc(inner);
+                        let method = availabilitySvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
                             accept_compression_encodings,
@@ -693,18 +2661,21 @@ pub mod reservation_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/reservation.ReservationService/get" => {
+                "/reservation.ReservationService/create_resource" => {
                     #[allow(non_camel_case_types)]
-                    struct getSvc<T: ReservationService>(pub Arc<T>);
-                    impl<T: ReservationService> tonic::server::UnaryService<super::GetRequest> for getSvc<T> {
-                        type Response = super::GetResponse;
+                    struct create_resourceSvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService>
+                        tonic::server::UnaryService<super::CreateResourceRequest>
+                        for create_resourceSvc<T>
+                    {
+                        type Response = super::CreateResourceResponse;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::GetRequest>,
+                            request: tonic::Request<super::CreateResourceRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).get(request).await };
+                            let fut = async move { (*inner).create_resource(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -713,7 +2684,7 @@ pub mod reservation_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = getSvc(inner);
+                        let method = create_resourceSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
                             accept_compression_encodings,
@@ -724,22 +2695,21 @@ pub mod reservation_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/reservation.ReservationService/query" => {
+                "/reservation.ReservationService/get_resource" => {
                     #[allow(non_camel_case_types)]
-                    struct querySvc<T: ReservationService>(pub Arc<T>);
+                    struct get_resourceSvc<T: ReservationService>(pub Arc<T>);
                     impl<T: ReservationService>
-                        tonic::server::ServerStreamingService<super::QueryRequest> for querySvc<T>
+                        tonic::server::UnaryService<super::GetResourceRequest>
+                        for get_resourceSvc<T>
                     {
-                        type Response = super::Reservation;
-                        type ResponseStream = T::queryStream;
-                        type Future =
-                            BoxFuture<tonic::Response<Self::ResponseStream>, tonic::Status>;
+                        type Response = super::GetResourceResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::QueryRequest>,
+                            request: tonic::Request<super::GetResourceRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).query(request).await };
+                            let fut = async move { (*inner).get_resource(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -748,29 +2718,32 @@ pub mod reservation_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = querySvc(inner);
+                        let method = get_resourceSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
                             accept_compression_encodings,
                             send_compression_encodings,
                         );
-                        let res = grpc.server_streaming(method, req).await;
+                        let res = grpc.unary(method, req).await;
                         Ok(res)
                     };
                     Box::pin(fut)
                 }
-                "/reservation.ReservationService/filter" => {
+                "/reservation.ReservationService/update_resource" => {
                     #[allow(non_camel_case_types)]
-                    struct filterSvc<T: ReservationService>(pub Arc<T>);
-                    impl<T: ReservationService> tonic::server::UnaryService<super::FilterRequest> for filterSvc<T> {
-                        type Response = super::FilterResponse;
+                    struct update_resourceSvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService>
+                        tonic::server::UnaryService<super::UpdateResourceRequest>
+                        for update_resourceSvc<T>
+                    {
+                        type Response = super::UpdateResourceResponse;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::FilterRequest>,
+                            request: tonic::Request<super::UpdateResourceRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).filter(request).await };
+                            let fut = async move { (*inner).update_resource(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -779,7 +2752,7 @@ pub mod reservation_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = filterSvc(inner);
+                        let method = update_resourceSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
                             accept_compression_encodings,
@@ -790,23 +2763,21 @@ pub mod reservation_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/reservation.ReservationService/listen" => {
+                "/reservation.ReservationService/delete_resource" => {
                     #[allow(non_camel_case_types)]
-                    struct listenSvc<T: ReservationService>(pub Arc<T>);
+                    struct delete_resourceSvc<T: ReservationService>(pub Arc<T>);
                     impl<T: ReservationService>
-                        tonic::server::ServerStreamingService<super::ListenRequest>
-                        for listenSvc<T>
+                        tonic::server::UnaryService<super::DeleteResourceRequest>
+                        for delete_resourceSvc<T>
                     {
-                        type Response = super::Reservation;
-                        type ResponseStream = T::listenStream;
-                        type Future =
-                            BoxFuture<tonic::Response<Self::ResponseStream>, tonic::Status>;
+                        type Response = super::DeleteResourceResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::ListenRequest>,
+                            request: tonic::Request<super::DeleteResourceRequest>,
                         ) -> Self::Future {
                             let inner = self.0.clone();
-                            let fut = async move { (*inner).listen(request).await };
+                            let fut = async move { (*inner).delete_resource(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -815,13 +2786,149 @@ pub mod reservation_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = listenSvc(inner);
+                        let method = delete_resourceSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
                             accept_compression_encodings,
                             send_compression_encodings,
                         );
-                        let res = grpc.server_streaming(method, req).await;
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/list_resources" => {
+                    #[allow(non_camel_case_types)]
+                    struct list_resourcesSvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService>
+                        tonic::server::UnaryService<super::ListResourcesRequest>
+                        for list_resourcesSvc<T>
+                    {
+                        type Response = super::ListResourcesResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListResourcesRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).list_resources(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = list_resourcesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/add_blackout" => {
+                    #[allow(non_camel_case_types)]
+                    struct add_blackoutSvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService>
+                        tonic::server::UnaryService<super::AddBlackoutRequest>
+                        for add_blackoutSvc<T>
+                    {
+                        type Response = super::AddBlackoutResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::AddBlackoutRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).add_blackout(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = add_blackoutSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/remove_blackout" => {
+                    #[allow(non_camel_case_types)]
+                    struct remove_blackoutSvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService>
+                        tonic::server::UnaryService<super::RemoveBlackoutRequest>
+                        for remove_blackoutSvc<T>
+                    {
+                        type Response = super::RemoveBlackoutResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RemoveBlackoutRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).remove_blackout(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = remove_blackoutSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/list_blackouts" => {
+                    #[allow(non_camel_case_types)]
+                    struct list_blackoutsSvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService>
+                        tonic::server::UnaryService<super::ListBlackoutsRequest>
+                        for list_blackoutsSvc<T>
+                    {
+                        type Response = super::ListBlackoutsResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListBlackoutsRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).list_blackouts(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = list_blackoutsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
                         Ok(res)
                     };
                     Box::pin(fut)
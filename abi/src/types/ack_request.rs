@@ -0,0 +1,41 @@
+use crate::{AckRequest, Error, Validator};
+
+impl AckRequest {
+    pub fn new(consumer_id: impl Into<String>, change_id: i64) -> Self {
+        Self {
+            consumer_id: consumer_id.into(),
+            change_id,
+        }
+    }
+}
+
+impl Validator for AckRequest {
+    fn validate(&self) -> Result<(), Error> {
+        // consumer id is stored in rsvp.server_read_cursor.server_id VARCHAR(64)
+        if self.consumer_id.is_empty() || self.consumer_id.len() > 64 {
+            return Err(Error::InvalidConsumerId(self.consumer_id.clone()));
+        }
+        if self.change_id <= 0 {
+            return Err(Error::InvalidCursor(self.change_id));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ack_request_should_validate_consumer_and_change_id() {
+        assert!(AckRequest::new("billing", 42).validate().is_ok());
+        assert_eq!(
+            AckRequest::new("", 42).validate(),
+            Err(Error::InvalidConsumerId("".into()))
+        );
+        assert_eq!(
+            AckRequest::new("billing", 0).validate(),
+            Err(Error::InvalidCursor(0))
+        );
+    }
+}
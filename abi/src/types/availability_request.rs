@@ -0,0 +1,344 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use sqlx::{postgres::types::PgRange, Postgres, QueryBuilder};
+
+use super::{get_timespan, validate_range};
+use crate::{
+    convert_to_utc_time, AvailabilityRequest, AvailabilityRequestBuilder, Error, FreeSlot,
+    Normalizer, ToSql, Validator,
+};
+
+/// a busy interval of a resource, clipped to the search window
+pub type BusySlot = (String, DateTime<Utc>, DateTime<Utc>);
+
+type Interval = (DateTime<Utc>, DateTime<Utc>);
+
+impl AvailabilityRequestBuilder {
+    pub fn build(&self) -> Result<AvailabilityRequest, Error> {
+        let mut request = self
+            .private_build()
+            .expect("failed to build AvailabilityRequest");
+        request.normalize()?;
+        Ok(request)
+    }
+}
+
+impl Validator for AvailabilityRequest {
+    fn validate(&self) -> Result<(), Error> {
+        if self.resource_ids.is_empty() && self.group_id.is_empty() {
+            return Err(Error::InvalidResourceId("".into()));
+        }
+        if let Some(id) = self.resource_ids.iter().find(|id| id.is_empty()) {
+            return Err(Error::InvalidResourceId(id.clone()));
+        }
+
+        validate_range(self.start.as_ref(), self.end.as_ref())?;
+
+        if self.min_duration_secs < 0 {
+            return Err(Error::InvalidDuration(self.min_duration_secs));
+        }
+
+        Ok(())
+    }
+}
+
+impl Normalizer for AvailabilityRequest {
+    fn do_normalize(&mut self) {
+        self.resource_ids.sort_unstable();
+        self.resource_ids.dedup();
+    }
+}
+
+impl AvailabilityRequest {
+    /// compute the free slots from the busy intervals returned by to_sql(). A resource is free
+    /// while it has fewer reservations than its capacity and it is not blocked, e.g. outside
+    /// of its opening hours. Resources missing in `capacities` have a capacity of 1
+    pub fn free_slots(
+        &self,
+        busy: &[BusySlot],
+        blocked: &[BusySlot],
+        capacities: &HashMap<String, i32>,
+    ) -> Vec<FreeSlot> {
+        let (window_start, window_end) = self.window();
+
+        let mut busy_by_resource: HashMap<&str, Vec<Interval>> = HashMap::new();
+        for (resource_id, start, end) in busy {
+            busy_by_resource
+                .entry(resource_id.as_str())
+                .or_default()
+                .push((*start, *end));
+        }
+        let mut blocked_by_resource: HashMap<&str, Vec<Interval>> = HashMap::new();
+        for (resource_id, start, end) in blocked {
+            blocked_by_resource
+                .entry(resource_id.as_str())
+                .or_default()
+                .push((*start, *end));
+        }
+
+        let mut slots = Vec::new();
+        for resource_id in &self.resource_ids {
+            let capacity = capacities.get(resource_id).copied().unwrap_or(1).max(1);
+            let intervals = busy_by_resource.remove(resource_id.as_str());
+            let blocks = blocked_by_resource.remove(resource_id.as_str());
+
+            // +1 when a reservation starts and -1 when it ends, ends sort first at the same time.
+            // A block takes the whole capacity
+            let mut events: Vec<(DateTime<Utc>, i32)> = intervals
+                .into_iter()
+                .flatten()
+                .flat_map(|(start, end)| [(start, 1), (end, -1)])
+                .chain(
+                    blocks
+                        .into_iter()
+                        .flatten()
+                        .flat_map(|(start, end)| [(start, capacity), (end, -capacity)]),
+                )
+                .collect();
+            events.sort_unstable();
+            events.push((window_end, 0));
+
+            let mut used = 0;
+            let mut cursor = window_start;
+            // start of the current free slot and the least remaining capacity in it
+            let mut current: Option<(DateTime<Utc>, i32)> = None;
+            for (time, delta) in events {
+                if time > cursor {
+                    if used < capacity {
+                        let remaining = capacity - used;
+                        current = Some(match current {
+                            Some((start, least)) => (start, least.min(remaining)),
+                            None => (cursor, remaining),
+                        });
+                    } else if let Some((start, remaining)) = current.take() {
+                        self.push_slot(&mut slots, resource_id, start, cursor, remaining);
+                    }
+                    cursor = time;
+                }
+                used += delta;
+            }
+            if let Some((start, remaining)) = current {
+                self.push_slot(&mut slots, resource_id, start, window_end, remaining);
+            }
+        }
+        slots
+    }
+
+    /// start and end of the search window
+    pub fn window(&self) -> Interval {
+        (
+            convert_to_utc_time(self.start.as_ref().unwrap()),
+            convert_to_utc_time(self.end.as_ref().unwrap()),
+        )
+    }
+
+    pub fn get_timespan(&self) -> PgRange<DateTime<Utc>> {
+        get_timespan(self.start.as_ref(), self.end.as_ref())
+    }
+
+    fn push_slot(
+        &self,
+        slots: &mut Vec<FreeSlot>,
+        resource_id: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        remaining: i32,
+    ) {
+        if start < end && (end - start).num_seconds() >= self.min_duration_secs {
+            slots.push(FreeSlot::new(resource_id, start, end).with_remaining(remaining));
+        }
+    }
+}
+
+impl ToSql for AvailabilityRequest {
+    /// every reservation of the resources blocks its timespan except the cancelled ones
+    fn to_sql(&self) -> QueryBuilder<'_, Postgres> {
+        let window = get_timespan(self.start.as_ref(), self.end.as_ref());
+        let mut builder = QueryBuilder::new(
+            "SELECT resource_id, lower(timespan * w.span) AS start, upper(timespan * w.span) AS end FROM rsvp.reservations, (SELECT ",
+        );
+        builder
+            .push_bind(window)
+            .push("::tstzrange AS span) w WHERE resource_id = ANY(")
+            .push_bind(&self.resource_ids[..])
+            .push(") AND timespan && w.span AND status <> 'cancelled' ORDER BY resource_id, lower(timespan)");
+        builder
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prost_types::Timestamp;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    fn ts(s: &str) -> Timestamp {
+        s.parse().unwrap()
+    }
+
+    fn make_request(min_duration_secs: i64) -> AvailabilityRequest {
+        AvailabilityRequestBuilder::default()
+            .resource_ids(vec!["room-2".to_string(), "room-1".to_string()])
+            .start(ts("2022-12-01T00:00:00Z"))
+            .end(ts("2022-12-02T00:00:00Z"))
+            .min_duration_secs(min_duration_secs)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn availability_request_should_validate() {
+        let err = AvailabilityRequestBuilder::default()
+            .start(ts("2022-12-01T00:00:00Z"))
+            .end(ts("2022-12-02T00:00:00Z"))
+            .build()
+            .unwrap_err();
+        assert_eq!(err, Error::InvalidResourceId("".into()));
+
+        // a group is enough, it is expanded to its members by the manager
+        assert!(AvailabilityRequestBuilder::default()
+            .group_id("floor-3")
+            .start(ts("2022-12-01T00:00:00Z"))
+            .end(ts("2022-12-02T00:00:00Z"))
+            .build()
+            .is_ok());
+
+        let err = AvailabilityRequestBuilder::default()
+            .resource_ids(vec!["room-1".to_string()])
+            .start(ts("2022-12-02T00:00:00Z"))
+            .end(ts("2022-12-01T00:00:00Z"))
+            .build()
+            .unwrap_err();
+        assert_eq!(err, Error::InvalidTime);
+
+        let err = AvailabilityRequestBuilder::default()
+            .resource_ids(vec!["room-1".to_string()])
+            .start(ts("2022-12-01T00:00:00Z"))
+            .end(ts("2022-12-02T00:00:00Z"))
+            .min_duration_secs(-1)
+            .build()
+            .unwrap_err();
+        assert_eq!(err, Error::InvalidDuration(-1));
+    }
+
+    #[test]
+    fn availability_request_should_generate_correct_sql() {
+        let request = make_request(0);
+        assert_eq!(request.resource_ids, vec!["room-1", "room-2"]);
+        assert_eq!(request.to_sql().into_sql(), "SELECT resource_id, lower(timespan * w.span) AS start, upper(timespan * w.span) AS end FROM rsvp.reservations, (SELECT $1::tstzrange AS span) w WHERE resource_id = ANY($2) AND timespan && w.span AND status <> 'cancelled' ORDER BY resource_id, lower(timespan)");
+    }
+
+    #[test]
+    fn free_slots_should_fill_the_gaps() {
+        let request = make_request(0);
+        let busy = vec![
+            (
+                "room-1".to_string(),
+                utc("2022-12-01T00:00:00Z"),
+                utc("2022-12-01T08:00:00Z"),
+            ),
+            (
+                "room-1".to_string(),
+                utc("2022-12-01T10:00:00Z"),
+                utc("2022-12-01T12:00:00Z"),
+            ),
+        ];
+        let slots = request.free_slots(&busy, &[], &HashMap::new());
+        assert_eq!(
+            slots,
+            vec![
+                FreeSlot::new(
+                    "room-1",
+                    utc("2022-12-01T08:00:00Z"),
+                    utc("2022-12-01T10:00:00Z")
+                ),
+                FreeSlot::new(
+                    "room-1",
+                    utc("2022-12-01T12:00:00Z"),
+                    utc("2022-12-02T00:00:00Z")
+                ),
+                FreeSlot::new(
+                    "room-2",
+                    utc("2022-12-01T00:00:00Z"),
+                    utc("2022-12-02T00:00:00Z")
+                ),
+            ]
+        );
+
+        // slots shorter than 3 hours are dropped
+        let request = make_request(3 * 3600);
+        let slots = request.free_slots(&busy, &[], &HashMap::new());
+        assert_eq!(slots.len(), 2);
+        assert_eq!(slots[0].start, Some(ts("2022-12-01T12:00:00Z")));
+        assert_eq!(slots[1].resource_id, "room-2");
+    }
+
+    #[test]
+    fn free_slots_should_respect_capacity() {
+        let request = make_request(0);
+        let busy: Vec<BusySlot> = [
+            ("2022-12-01T08:00:00Z", "2022-12-01T12:00:00Z"),
+            ("2022-12-01T10:00:00Z", "2022-12-01T14:00:00Z"),
+        ]
+        .iter()
+        .map(|(start, end)| ("room-1".to_string(), utc(start), utc(end)))
+        .collect();
+
+        // two desks are full while both reservations overlap
+        let capacities = HashMap::from([("room-1".to_string(), 2)]);
+        let slots = request.free_slots(&busy, &[], &capacities);
+        assert_eq!(
+            slots,
+            vec![
+                FreeSlot::new(
+                    "room-1",
+                    utc("2022-12-01T00:00:00Z"),
+                    utc("2022-12-01T10:00:00Z")
+                ),
+                FreeSlot::new(
+                    "room-1",
+                    utc("2022-12-01T12:00:00Z"),
+                    utc("2022-12-02T00:00:00Z")
+                ),
+                FreeSlot::new(
+                    "room-2",
+                    utc("2022-12-01T00:00:00Z"),
+                    utc("2022-12-02T00:00:00Z")
+                ),
+            ]
+        );
+
+        // three desks are never full, the whole window has at least one left
+        let capacities = HashMap::from([("room-1".to_string(), 3)]);
+        let slots = request.free_slots(&busy, &[], &capacities);
+        assert_eq!(
+            slots[0],
+            FreeSlot::new(
+                "room-1",
+                utc("2022-12-01T00:00:00Z"),
+                utc("2022-12-02T00:00:00Z")
+            )
+            .with_remaining(1)
+        );
+
+        // nothing is free while blocked, whatever the capacity
+        let blocked = vec![(
+            "room-2".to_string(),
+            utc("2022-12-01T00:00:00Z"),
+            utc("2022-12-01T12:00:00Z"),
+        )];
+        let slots = request.free_slots(&busy, &blocked, &capacities);
+        assert_eq!(
+            slots[1],
+            FreeSlot::new(
+                "room-2",
+                utc("2022-12-01T12:00:00Z"),
+                utc("2022-12-02T00:00:00Z")
+            )
+        );
+    }
+}
@@ -0,0 +1,94 @@
+use chrono::{DateTime, FixedOffset, Utc};
+use sqlx::{
+    postgres::{types::PgRange, PgRow},
+    FromRow, Row,
+};
+
+use super::{get_timespan, reservation::NaiveRange, validate_range};
+use crate::{convert_to_timestamp, convert_to_utc_time, Blackout, Error, Validator};
+
+impl Blackout {
+    pub fn new(
+        resource_id: impl Into<String>,
+        start: DateTime<FixedOffset>,
+        end: DateTime<FixedOffset>,
+        reason: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: 0,
+            resource_id: resource_id.into(),
+            start: Some(convert_to_timestamp(&start.with_timezone(&Utc))),
+            end: Some(convert_to_timestamp(&end.with_timezone(&Utc))),
+            reason: reason.into(),
+        }
+    }
+
+    pub fn get_timespan(&self) -> PgRange<DateTime<Utc>> {
+        get_timespan(self.start.as_ref(), self.end.as_ref())
+    }
+}
+
+impl Validator for Blackout {
+    fn validate(&self) -> Result<(), Error> {
+        if self.resource_id.is_empty() {
+            return Err(Error::InvalidResourceId(self.resource_id.clone()));
+        }
+        validate_range(self.start.as_ref(), self.end.as_ref())
+    }
+}
+
+impl FromRow<'_, PgRow> for Blackout {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        let range: PgRange<DateTime<Utc>> = row.get("timespan");
+        let range: NaiveRange<DateTime<Utc>> = range.into();
+        Ok(Self {
+            id: row.get("id"),
+            resource_id: row.get("resource_id"),
+            start: range.start.as_ref().map(convert_to_timestamp),
+            end: range.end.as_ref().map(convert_to_timestamp),
+            reason: row.get("reason"),
+        })
+    }
+}
+
+/// the reservation overlaps the blackout
+impl From<Blackout> for Error {
+    fn from(blackout: Blackout) -> Self {
+        Error::ResourceBlackedOut(
+            blackout.resource_id,
+            convert_to_utc_time(blackout.start.as_ref().unwrap()),
+            convert_to_utc_time(blackout.end.as_ref().unwrap()),
+            blackout.reason,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blackout_should_validate() {
+        let blackout = Blackout::new(
+            "lab-1",
+            "2022-12-24T00:00:00-0800".parse().unwrap(),
+            "2022-12-27T00:00:00-0800".parse().unwrap(),
+            "holidays",
+        );
+        assert!(blackout.validate().is_ok());
+
+        let err = Blackout {
+            end: blackout.start.clone(),
+            ..blackout.clone()
+        }
+        .validate()
+        .unwrap_err();
+        assert_eq!(err, Error::InvalidTime);
+
+        let err = Error::from(blackout);
+        assert_eq!(
+            err.to_string(),
+            "Resource lab-1 is blacked out from 2022-12-24 08:00:00 UTC to 2022-12-27 08:00:00 UTC: holidays"
+        );
+    }
+}
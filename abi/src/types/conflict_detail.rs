@@ -0,0 +1,191 @@
+use prost::Message;
+use tonic::{codegen::Bytes, Code, Status};
+
+use crate::{
+    convert_to_timestamp, ConflictDetail, ConflictWindow, ErrorCode, ReservationConflictInfo,
+    ReservationWindow, RpcStatus,
+};
+
+const CONFLICT_DETAIL_TYPE_URL: &str = "type.googleapis.com/reservation.ConflictDetail";
+
+impl From<&ReservationWindow> for ConflictWindow {
+    fn from(window: &ReservationWindow) -> Self {
+        Self {
+            resource_id: window.rid.clone(),
+            start: Some(convert_to_timestamp(&window.start)),
+            end: Some(convert_to_timestamp(&window.end)),
+        }
+    }
+}
+
+impl From<&ReservationConflictInfo> for ConflictDetail {
+    fn from(info: &ReservationConflictInfo) -> Self {
+        match info {
+            ReservationConflictInfo::Parsed(conflict) => Self {
+                code: ErrorCode::ReservationConflict as i32,
+                new: Some((&conflict.new).into()),
+                old: Some((&conflict.old).into()),
+                conflicting_id: conflict.conflicting_id.unwrap_or_default(),
+                ..Default::default()
+            },
+            ReservationConflictInfo::Full(full) => Self {
+                code: ErrorCode::ResourceFull as i32,
+                new: Some((&full.new).into()),
+                old: Some((&full.full).into()),
+                capacity: full.capacity,
+                ..Default::default()
+            },
+            ReservationConflictInfo::Unavailable(unavailable) => Self {
+                code: ErrorCode::ResourceUnavailable as i32,
+                new: Some((&unavailable.new).into()),
+                reason: unavailable.reason.clone(),
+                ..Default::default()
+            },
+            ReservationConflictInfo::Unparsed(_) => Self {
+                code: ErrorCode::ReservationConflict as i32,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl ConflictDetail {
+    /// build a FAILED_PRECONDITION status carrying the detail as google.rpc.Status
+    pub fn into_status(self, message: impl Into<String>) -> Status {
+        Self::into_status_all(vec![self], message)
+    }
+
+    /// build a FAILED_PRECONDITION status carrying all the details in one google.rpc.Status
+    pub fn into_status_all(details: Vec<Self>, message: impl Into<String>) -> Status {
+        let message = message.into();
+        let details = RpcStatus {
+            code: Code::FailedPrecondition as i32,
+            message: message.clone(),
+            details: details
+                .iter()
+                .map(|detail| prost_types::Any {
+                    type_url: CONFLICT_DETAIL_TYPE_URL.into(),
+                    value: detail.encode_to_vec(),
+                })
+                .collect(),
+        };
+        Status::with_details(
+            Code::FailedPrecondition,
+            message,
+            Bytes::from(details.encode_to_vec()),
+        )
+    }
+
+    /// decode the conflict detail from a status returned by the server, None if there is none
+    pub fn from_status(status: &Status) -> Option<Self> {
+        Self::all_from_status(status).into_iter().next()
+    }
+
+    /// decode all the conflict details from a status returned by the server
+    pub fn all_from_status(status: &Status) -> Vec<Self> {
+        let Ok(details) = RpcStatus::decode(status.details()) else {
+            return vec![];
+        };
+        details
+            .details
+            .iter()
+            .filter(|any| any.type_url == CONFLICT_DETAIL_TYPE_URL)
+            .filter_map(|any| Self::decode(any.value.as_slice()).ok())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Error, ReservationConflict, ResourceFull};
+
+    #[test]
+    fn conflict_error_should_carry_detail_in_status() {
+        let new = ReservationWindow {
+            rid: "ocean-view-room-713".into(),
+            start: "2022-12-26T22:00:00Z".parse().unwrap(),
+            end: "2022-12-30T19:00:00Z".parse().unwrap(),
+        };
+        let old = ReservationWindow {
+            rid: "ocean-view-room-713".into(),
+            start: "2022-12-25T22:00:00Z".parse().unwrap(),
+            end: "2022-12-28T19:00:00Z".parse().unwrap(),
+        };
+        let info = ReservationConflictInfo::Parsed(ReservationConflict {
+            new: new.clone(),
+            old: old.clone(),
+            conflicting_id: Some(42),
+        });
+        let status: Status = Error::ConflictReservation(info).into();
+        assert_eq!(status.code(), Code::FailedPrecondition);
+
+        let detail = ConflictDetail::from_status(&status).unwrap();
+        assert_eq!(detail.code, ErrorCode::ReservationConflict as i32);
+        assert_eq!(detail.new, Some((&new).into()));
+        assert_eq!(detail.old, Some((&old).into()));
+        assert_eq!(detail.conflicting_id, 42);
+    }
+
+    #[test]
+    fn conflicts_error_should_carry_every_detail_in_status() {
+        let window = |rid: &str| ReservationWindow {
+            rid: rid.into(),
+            start: "2022-12-26T22:00:00Z".parse().unwrap(),
+            end: "2022-12-30T19:00:00Z".parse().unwrap(),
+        };
+        let infos = ["room-1", "projector-1"]
+            .iter()
+            .map(|rid| {
+                ReservationConflictInfo::Parsed(ReservationConflict {
+                    new: window(rid),
+                    old: window(rid),
+                    conflicting_id: None,
+                })
+            })
+            .collect();
+        let status: Status = Error::ConflictReservations(infos).into();
+        assert_eq!(status.code(), Code::FailedPrecondition);
+
+        let details = ConflictDetail::all_from_status(&status);
+        assert_eq!(details.len(), 2);
+        assert_eq!(details[1].new, Some((&window("projector-1")).into()));
+        assert_eq!(details[1].conflicting_id, 0);
+    }
+
+    #[test]
+    fn full_error_should_carry_full_interval_in_status() {
+        let window = |start: &str, end: &str| ReservationWindow {
+            rid: "desk-pool-1".into(),
+            start: start.parse().unwrap(),
+            end: end.parse().unwrap(),
+        };
+        let info = ReservationConflictInfo::Full(ResourceFull {
+            new: window("2022-12-26T09:00:00Z", "2022-12-26T12:00:00Z"),
+            full: window("2022-12-26T10:00:00Z", "2022-12-26T11:00:00Z"),
+            capacity: 2,
+        });
+        let status: Status = Error::ConflictReservation(info).into();
+        assert_eq!(status.code(), Code::FailedPrecondition);
+
+        let detail = ConflictDetail::from_status(&status).unwrap();
+        assert_eq!(detail.code, ErrorCode::ResourceFull as i32);
+        assert_eq!(
+            detail.old,
+            Some((&window("2022-12-26T10:00:00Z", "2022-12-26T11:00:00Z")).into())
+        );
+        assert_eq!(detail.capacity, 2);
+    }
+
+    #[test]
+    fn status_without_conflict_should_have_no_detail() {
+        let status: Status = Error::NotFound.into();
+        assert_eq!(ConflictDetail::from_status(&status), None);
+
+        let status: Status =
+            Error::ConflictReservation(ReservationConflictInfo::Unparsed("oops".into())).into();
+        let detail = ConflictDetail::from_status(&status).unwrap();
+        assert_eq!(detail.code, ErrorCode::ReservationConflict as i32);
+        assert_eq!(detail.new, None);
+    }
+}
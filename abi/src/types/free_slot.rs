@@ -0,0 +1,19 @@
+use chrono::{DateTime, Utc};
+
+use crate::{convert_to_timestamp, FreeSlot};
+
+impl FreeSlot {
+    pub fn new(resource_id: impl Into<String>, start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        Self {
+            resource_id: resource_id.into(),
+            start: Some(convert_to_timestamp(&start)),
+            end: Some(convert_to_timestamp(&end)),
+            remaining: 1,
+        }
+    }
+
+    pub fn with_remaining(mut self, remaining: i32) -> Self {
+        self.remaining = remaining;
+        self
+    }
+}
@@ -0,0 +1,152 @@
+use std::collections::VecDeque;
+
+use sqlx::{Postgres, QueryBuilder};
+
+use crate::{
+    pager::{Id, PageInfo, Paginator},
+    Error, FilterPager, HistoryRequest, HistoryRequestBuilder, ReservationId, ToSql, Validator,
+};
+
+impl HistoryRequestBuilder {
+    pub fn build(&self) -> Result<HistoryRequest, Error> {
+        let request = self
+            .private_build()
+            .expect("failed to build HistoryRequest");
+        request.validate()?;
+        Ok(request)
+    }
+}
+
+impl Validator for HistoryRequest {
+    fn validate(&self) -> Result<(), Error> {
+        (self.id as ReservationId).validate()?;
+
+        if self.page_size < 10 || self.page_size > 100 {
+            return Err(Error::InvalidPageSize(self.page_size));
+        }
+
+        if let Some(cursor) = self.cursor {
+            if cursor < 0 {
+                return Err(Error::InvalidCursor(cursor));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl HistoryRequest {
+    pub fn get_cursor(&self) -> i64 {
+        self.cursor.unwrap_or(if self.desc { i64::MAX } else { 0 })
+    }
+
+    pub fn get_pager<T: Id>(&self, data: &mut VecDeque<T>) -> FilterPager {
+        let page_info = self.page_info();
+        let pager = page_info.get_pager(data);
+
+        pager.into()
+    }
+
+    pub fn next_page(&self, pager: &FilterPager) -> Option<Self> {
+        let page_info = self.page_info();
+        let pager = pager.into();
+        let page_info = page_info.next_page(&pager);
+        page_info.map(|page_info| Self {
+            id: self.id,
+            cursor: page_info.cursor,
+            page_size: page_info.page_size,
+            desc: page_info.desc,
+        })
+    }
+
+    fn page_info(&self) -> PageInfo {
+        PageInfo {
+            cursor: self.cursor,
+            page_size: self.page_size,
+            desc: self.desc,
+        }
+    }
+}
+
+impl ToSql for HistoryRequest {
+    /// each change is returned as one row per snapshot (old and/or new), the snapshot is
+    /// expanded into the columns of rsvp.reservations
+    fn to_sql(&self) -> QueryBuilder<'_, Postgres> {
+        let middle_plus = if self.cursor.is_none() { 0 } else { 1 };
+        let limit = self.page_size + 1 + middle_plus;
+        let cursor_cond = if self.desc {
+            format!("id <= {}", self.get_cursor())
+        } else {
+            format!("id >= {}", self.get_cursor())
+        };
+
+        let direction = if self.desc { "DESC" } else { "ASC" };
+        QueryBuilder::new(format!("WITH changes AS (SELECT * FROM rsvp.reservation_changes WHERE reservation_id = {} AND {} ORDER BY id {} LIMIT {}) \
+            SELECT c.id::int8 AS change_id, c.op, 'old' AS side, r.* FROM changes c, jsonb_populate_record(NULL::rsvp.reservations, c.old) r WHERE c.old IS NOT NULL \
+            UNION ALL \
+            SELECT c.id::int8 AS change_id, c.op, 'new' AS side, r.* FROM changes c, jsonb_populate_record(NULL::rsvp.reservations, c.new) r WHERE c.new IS NOT NULL \
+            ORDER BY change_id {}",
+            self.id, cursor_cond, direction, limit, direction))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pager::pager_test_utils;
+
+    #[test]
+    fn history_request_should_validate() {
+        let err = HistoryRequestBuilder::default().build().unwrap_err();
+        assert_eq!(err, Error::InvalidReservationId(0));
+
+        let err = HistoryRequestBuilder::default()
+            .id(1)
+            .page_size(5)
+            .build()
+            .unwrap_err();
+        assert_eq!(err, Error::InvalidPageSize(5));
+
+        let err = HistoryRequestBuilder::default()
+            .id(1)
+            .cursor(-1)
+            .build()
+            .unwrap_err();
+        assert_eq!(err, Error::InvalidCursor(-1));
+    }
+
+    #[test]
+    fn history_request_should_generate_correct_sql() {
+        let request = HistoryRequestBuilder::default().id(42).build().unwrap();
+        assert_eq!(request.to_sql().into_sql(), "WITH changes AS (SELECT * FROM rsvp.reservation_changes WHERE reservation_id = 42 AND id >= 0 ORDER BY id ASC LIMIT 11) \
+            SELECT c.id::int8 AS change_id, c.op, 'old' AS side, r.* FROM changes c, jsonb_populate_record(NULL::rsvp.reservations, c.old) r WHERE c.old IS NOT NULL \
+            UNION ALL \
+            SELECT c.id::int8 AS change_id, c.op, 'new' AS side, r.* FROM changes c, jsonb_populate_record(NULL::rsvp.reservations, c.new) r WHERE c.new IS NOT NULL \
+            ORDER BY change_id ASC");
+
+        let request = HistoryRequestBuilder::default()
+            .id(42)
+            .cursor(100)
+            .desc(true)
+            .build()
+            .unwrap();
+        assert_eq!(request.to_sql().into_sql(), "WITH changes AS (SELECT * FROM rsvp.reservation_changes WHERE reservation_id = 42 AND id <= 100 ORDER BY id DESC LIMIT 12) \
+            SELECT c.id::int8 AS change_id, c.op, 'old' AS side, r.* FROM changes c, jsonb_populate_record(NULL::rsvp.reservations, c.old) r WHERE c.old IS NOT NULL \
+            UNION ALL \
+            SELECT c.id::int8 AS change_id, c.op, 'new' AS side, r.* FROM changes c, jsonb_populate_record(NULL::rsvp.reservations, c.new) r WHERE c.new IS NOT NULL \
+            ORDER BY change_id DESC");
+    }
+
+    #[test]
+    fn history_request_with_pager_should_work() {
+        let request = HistoryRequestBuilder::default().id(42).build().unwrap();
+        let mut data = pager_test_utils::generate_test_ids(1, 11);
+        let pager = request.get_pager(&mut data);
+        assert_eq!(pager.prev, None);
+        assert_eq!(pager.next, Some(10));
+
+        let request = request.next_page(&pager).unwrap();
+        assert_eq!(request.id, 42);
+        assert_eq!(request.cursor, Some(10));
+    }
+}
@@ -0,0 +1,163 @@
+use crate::{
+    Error, ListenRequest, ListenResponse, ReservationStatus, ReservationUpdateType, Validator,
+};
+
+impl ListenRequest {
+    /// listen for the given consumer, resuming after the last change it acknowledged
+    pub fn resume(consumer_id: impl Into<String>) -> Self {
+        Self {
+            consumer_id: Some(consumer_id.into()),
+            ..Default::default()
+        }
+    }
+
+    /// check if the change passes the filters of the request, empty filters match everything
+    pub fn matches(&self, change: &ListenResponse) -> bool {
+        if !self.ops.is_empty() && !self.ops.contains(&change.op) {
+            return false;
+        }
+
+        let Some(rsvp) = change.reservation.as_ref() else {
+            return false;
+        };
+
+        (self.resource_ids.is_empty() || self.resource_ids.contains(&rsvp.resource_id))
+            && rsvp.resource_id.starts_with(&self.resource_id_prefix)
+            && (self.user_id.is_empty() || self.user_id == rsvp.user_id)
+            && (self.statuses.is_empty() || self.statuses.contains(&rsvp.status))
+    }
+}
+
+impl Validator for ListenRequest {
+    fn validate(&self) -> Result<(), Error> {
+        if let Some(consumer_id) = self.consumer_id.as_ref() {
+            // consumer id is stored in rsvp.server_read_cursor.server_id VARCHAR(64)
+            if consumer_id.is_empty() || consumer_id.len() > 64 {
+                return Err(Error::InvalidConsumerId(consumer_id.clone()));
+            }
+        }
+
+        if let Some(last_change_id) = self.last_change_id {
+            if last_change_id < 0 {
+                return Err(Error::InvalidCursor(last_change_id));
+            }
+        }
+
+        for op in self.ops.iter() {
+            ReservationUpdateType::from_i32(*op).ok_or(Error::InvalidUpdateType(*op))?;
+        }
+
+        for status in self.statuses.iter() {
+            ReservationStatus::from_i32(*status).ok_or(Error::InvalidStatus(*status))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Reservation;
+
+    #[test]
+    fn listen_request_should_validate_consumer_id_and_cursor() {
+        assert!(ListenRequest::default().validate().is_ok());
+        assert!(ListenRequest::resume("billing").validate().is_ok());
+
+        let err = ListenRequest::resume("").validate().unwrap_err();
+        assert_eq!(err, Error::InvalidConsumerId("".into()));
+
+        let consumer_id = "c".repeat(65);
+        let err = ListenRequest::resume(consumer_id.clone())
+            .validate()
+            .unwrap_err();
+        assert_eq!(err, Error::InvalidConsumerId(consumer_id));
+
+        let request = ListenRequest {
+            last_change_id: Some(-1),
+            ..Default::default()
+        };
+        assert_eq!(request.validate().unwrap_err(), Error::InvalidCursor(-1));
+    }
+
+    #[test]
+    fn listen_request_should_validate_ops_and_statuses() {
+        let request = ListenRequest {
+            ops: vec![ReservationUpdateType::Create as i32, 10],
+            ..Default::default()
+        };
+        assert_eq!(
+            request.validate().unwrap_err(),
+            Error::InvalidUpdateType(10)
+        );
+
+        let request = ListenRequest {
+            statuses: vec![10],
+            ..Default::default()
+        };
+        assert_eq!(request.validate().unwrap_err(), Error::InvalidStatus(10));
+    }
+
+    #[test]
+    fn listen_request_should_match_changes() {
+        let change = ListenResponse {
+            op: ReservationUpdateType::Create as i32,
+            reservation: Some(Reservation::new_pending(
+                "alice",
+                "floor-3-room-301",
+                "2022-12-25T15:00:00-0700".parse().unwrap(),
+                "2022-12-28T12:00:00-0700".parse().unwrap(),
+                "",
+            )),
+            change_id: 1,
+        };
+
+        assert!(ListenRequest::default().matches(&change));
+
+        let request = ListenRequest {
+            resource_ids: vec!["floor-3-room-301".into(), "floor-3-room-302".into()],
+            user_id: "alice".into(),
+            ops: vec![ReservationUpdateType::Create as i32],
+            statuses: vec![ReservationStatus::Pending as i32],
+            ..Default::default()
+        };
+        assert!(request.matches(&change));
+
+        let request = ListenRequest {
+            resource_id_prefix: "floor-3-".into(),
+            ..Default::default()
+        };
+        assert!(request.matches(&change));
+
+        let request = ListenRequest {
+            resource_id_prefix: "floor-4-".into(),
+            ..Default::default()
+        };
+        assert!(!request.matches(&change));
+
+        let request = ListenRequest {
+            resource_ids: vec!["floor-3-room-302".into()],
+            ..Default::default()
+        };
+        assert!(!request.matches(&change));
+
+        let request = ListenRequest {
+            user_id: "bob".into(),
+            ..Default::default()
+        };
+        assert!(!request.matches(&change));
+
+        let request = ListenRequest {
+            ops: vec![ReservationUpdateType::Delete as i32],
+            ..Default::default()
+        };
+        assert!(!request.matches(&change));
+
+        let request = ListenRequest {
+            statuses: vec![ReservationStatus::Confirmed as i32],
+            ..Default::default()
+        };
+        assert!(!request.matches(&change));
+    }
+}
@@ -0,0 +1,15 @@
+use sqlx::{postgres::PgRow, FromRow, Row};
+
+use crate::{ListenResponse, Reservation, ReservationUpdateType, RsvpUpdateType};
+
+/// a row of rsvp.reservation_changes, with the reservation snapshot expanded into columns
+impl FromRow<'_, PgRow> for ListenResponse {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        let op: RsvpUpdateType = row.get("op");
+        Ok(Self {
+            op: ReservationUpdateType::from(op) as i32,
+            reservation: Some(Reservation::from_row(row)?),
+            change_id: row.get("change_id"),
+        })
+    }
+}
@@ -1,16 +1,31 @@
+mod ack_request;
+mod availability_request;
+mod blackout;
+mod conflict_detail;
+mod free_slot;
+mod history_request;
+mod listen_request;
+mod listen_response;
+mod opening_hours;
 mod request;
 mod reservation;
+mod reservation_change;
 mod reservation_filter;
 mod reservation_query;
+mod reservation_result;
 mod reservation_status;
+mod reservation_update_type;
+mod reserve_recurring_request;
+mod resource;
+mod series_selector;
 
 use std::ops::Bound;
 
 use chrono::{DateTime, Utc};
 use prost_types::Timestamp;
-use sqlx::postgres::types::PgRange;
+use sqlx::{postgres::types::PgRange, Postgres, QueryBuilder};
 
-use crate::{convert_to_utc_time, Error};
+use crate::{convert_to_utc_time, Error, ReservationStatus};
 
 pub fn validate_range(start: Option<&Timestamp>, end: Option<&Timestamp>) -> Result<(), Error> {
     if start.is_none() || end.is_none() {
@@ -36,6 +51,63 @@ pub fn get_timespan(start: Option<&Timestamp>, end: Option<&Timestamp>) -> PgRan
     }
 }
 
+pub fn validate_statuses(statuses: &[i32]) -> Result<(), Error> {
+    for status in statuses {
+        ReservationStatus::from_i32(*status).ok_or(Error::InvalidStatus(*status))?;
+    }
+    Ok(())
+}
+
+/// sort and dedup the statuses, a list containing UNKNOWN means all statuses and is cleared
+pub fn normalize_statuses(statuses: &mut Vec<i32>) {
+    if statuses.contains(&(ReservationStatus::Unknown as i32)) {
+        statuses.clear();
+    }
+    statuses.sort_unstable();
+    statuses.dedup();
+}
+
+/// condition on the status column, an empty list matches all statuses
+fn status_cond(statuses: &[i32]) -> String {
+    if statuses.is_empty() {
+        return "TRUE".into();
+    }
+
+    let statuses: Vec<String> = statuses
+        .iter()
+        .map(|s| format!("'{}'", ReservationStatus::from_i32(*s).unwrap()))
+        .collect();
+    format!("status IN ({})", statuses.join(", "))
+}
+
+/// push the condition on user_id and resource_id, empty ids are not used to filter
+fn push_user_resource_cond<'a>(
+    builder: &mut QueryBuilder<'a, Postgres>,
+    user_id: &'a str,
+    resource_id: &'a str,
+) {
+    match (user_id.is_empty(), resource_id.is_empty()) {
+        (true, true) => builder.push("TRUE"),
+        (true, false) => builder.push("resource_id = ").push_bind(resource_id),
+        (false, true) => builder.push("user_id = ").push_bind(user_id),
+        (false, false) => builder
+            .push("user_id = ")
+            .push_bind(user_id)
+            .push(" AND resource_id = ")
+            .push_bind(resource_id),
+    };
+}
+
+/// condition on the members of the group, nothing is pushed if the group is empty
+fn push_group_cond<'a>(builder: &mut QueryBuilder<'a, Postgres>, group_id: &'a str) {
+    if !group_id.is_empty() {
+        builder
+            .push(" AND resource_id IN (SELECT rsvp.resource_members(")
+            .push_bind(group_id)
+            .push("))");
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -70,6 +142,34 @@ mod tests {
         assert!(validate_range(Some(&start), Some(&end)).is_err())
     }
 
+    #[test]
+    fn normalize_statuses_should_treat_unknown_as_all() {
+        let mut statuses = vec![
+            ReservationStatus::Confirmed as i32,
+            ReservationStatus::Pending as i32,
+            ReservationStatus::Confirmed as i32,
+        ];
+        normalize_statuses(&mut statuses);
+        assert_eq!(
+            statuses,
+            vec![
+                ReservationStatus::Pending as i32,
+                ReservationStatus::Confirmed as i32
+            ]
+        );
+        assert_eq!(status_cond(&statuses), "status IN ('pending', 'confirmed')");
+
+        let mut statuses = vec![
+            ReservationStatus::Pending as i32,
+            ReservationStatus::Unknown as i32,
+        ];
+        normalize_statuses(&mut statuses);
+        assert!(statuses.is_empty());
+        assert_eq!(status_cond(&statuses), "TRUE");
+
+        assert_eq!(validate_statuses(&[10]), Err(Error::InvalidStatus(10)));
+    }
+
     #[test]
     fn get_timespan_should_work_for_valid_start_end() {
         let start = Timestamp {
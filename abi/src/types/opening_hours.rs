@@ -0,0 +1,219 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDateTime, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+
+use crate::{Error, OpeningHours, Resource, Validator};
+
+/// seconds in a day, the latest closing time
+const DAY_SECS: i32 = 24 * 3600;
+
+type Interval = (DateTime<Utc>, DateTime<Utc>);
+
+impl OpeningHours {
+    pub fn new(weekday: Weekday, opens_secs: i32, closes_secs: i32) -> Self {
+        Self {
+            weekday: weekday.number_from_monday() as i32,
+            opens_secs,
+            closes_secs,
+        }
+    }
+}
+
+impl Validator for OpeningHours {
+    fn validate(&self) -> Result<(), Error> {
+        if !(1..=7).contains(&self.weekday)
+            || self.opens_secs < 0
+            || self.opens_secs >= self.closes_secs
+            || self.closes_secs > DAY_SECS
+        {
+            return Err(Error::InvalidOpeningHours(self.weekday));
+        }
+        Ok(())
+    }
+}
+
+impl Resource {
+    /// timezone of the opening hours, empty is UTC
+    pub fn tz(&self) -> Result<Tz, Error> {
+        if self.timezone.is_empty() {
+            return Ok(Tz::UTC);
+        }
+        self.timezone
+            .parse()
+            .map_err(|_| Error::InvalidTimezone(self.timezone.clone()))
+    }
+
+    /// intervals of the window in which the resource is open, ordered and merged. The whole
+    /// window if the resource has no opening hours
+    pub fn open_intervals(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<Interval>, Error> {
+        if self.opening_hours.is_empty() {
+            return Ok(vec![(start, end)]);
+        }
+
+        let tz = self.tz()?;
+        // local dates could be a day before or after the UTC ones
+        let mut date = start.with_timezone(&tz).date_naive() - Duration::days(1);
+        let last = end.with_timezone(&tz).date_naive() + Duration::days(1);
+        let mut intervals = Vec::new();
+        while date <= last {
+            let weekday = date.weekday().number_from_monday() as i32;
+            let midnight = date.and_hms_opt(0, 0, 0).unwrap();
+            for hours in self.opening_hours.iter().filter(|h| h.weekday == weekday) {
+                let opens = to_utc(&tz, midnight + Duration::seconds(hours.opens_secs.into()));
+                let closes = to_utc(&tz, midnight + Duration::seconds(hours.closes_secs.into()));
+                let (opens, closes) = (opens.max(start), closes.min(end));
+                if opens < closes {
+                    intervals.push((opens, closes));
+                }
+            }
+            date += Duration::days(1);
+        }
+
+        intervals.sort_unstable();
+        let mut merged: Vec<Interval> = Vec::with_capacity(intervals.len());
+        for (opens, closes) in intervals {
+            match merged.last_mut() {
+                Some(last) if opens <= last.1 => last.1 = last.1.max(closes),
+                _ => merged.push((opens, closes)),
+            }
+        }
+        Ok(merged)
+    }
+
+    /// intervals of the window in which the resource is closed, ordered
+    pub fn closed_intervals(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<Interval>, Error> {
+        let mut closed = Vec::new();
+        let mut cursor = start;
+        for (opens, closes) in self.open_intervals(start, end)? {
+            if cursor < opens {
+                closed.push((cursor, opens));
+            }
+            cursor = closes;
+        }
+        if cursor < end {
+            closed.push((cursor, end));
+        }
+        Ok(closed)
+    }
+
+    /// fail with the first time of the window at which the resource is closed
+    pub fn check_open(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<(), Error> {
+        match self.closed_intervals(start, end)?.first() {
+            Some((closed, _)) => Err(Error::ResourceClosed(self.id.clone(), *closed)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// local time in UTC, a time skipped by a daylight saving change is moved forward by an hour
+fn to_utc(tz: &Tz, local: NaiveDateTime) -> DateTime<Utc> {
+    match tz.from_local_datetime(&local).earliest() {
+        Some(time) => time.with_timezone(&Utc),
+        None => to_utc(tz, local + Duration::hours(1)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: i32 = 3600;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    /// open 9:00 ~ 17:00 on weekdays in Los Angeles, and overnight from Friday to Saturday
+    fn make_lab() -> Resource {
+        let weekdays = [
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+        ];
+        let mut lab = Resource::new("lab-1", "Lab 1", "lab").with_opening_hours(
+            "America/Los_Angeles",
+            weekdays.map(|weekday| OpeningHours::new(weekday, 9 * HOUR, 17 * HOUR)),
+        );
+        lab.opening_hours
+            .push(OpeningHours::new(Weekday::Fri, 20 * HOUR, DAY_SECS));
+        lab.opening_hours
+            .push(OpeningHours::new(Weekday::Sat, 0, 6 * HOUR));
+        lab
+    }
+
+    #[test]
+    fn resource_should_validate_calendar() {
+        let lab = make_lab();
+        assert!(lab.validate().is_ok());
+
+        let err = Resource {
+            timezone: "Mars/Olympus_Mons".into(),
+            ..lab.clone()
+        }
+        .validate()
+        .unwrap_err();
+        assert_eq!(err, Error::InvalidTimezone("Mars/Olympus_Mons".into()));
+
+        let mut closed = lab;
+        closed.opening_hours[0].closes_secs = 0;
+        assert_eq!(closed.validate(), Err(Error::InvalidOpeningHours(1)));
+    }
+
+    #[test]
+    fn resource_should_only_be_open_in_opening_hours() {
+        let lab = make_lab();
+        // 2022-12-05 is a Monday, 9:00 PST is 17:00 UTC
+        assert!(lab
+            .check_open(utc("2022-12-05T17:00:00Z"), utc("2022-12-06T01:00:00Z"))
+            .is_ok());
+        assert_eq!(
+            lab.check_open(utc("2022-12-05T16:00:00Z"), utc("2022-12-05T18:00:00Z")),
+            Err(Error::ResourceClosed(
+                "lab-1".into(),
+                utc("2022-12-05T16:00:00Z")
+            ))
+        );
+        assert_eq!(
+            lab.check_open(utc("2022-12-05T23:00:00Z"), utc("2022-12-06T03:00:00Z")),
+            Err(Error::ResourceClosed(
+                "lab-1".into(),
+                utc("2022-12-06T01:00:00Z")
+            ))
+        );
+
+        // hours closing at midnight are joined with the ones opening at midnight
+        assert!(lab
+            .check_open(utc("2022-12-10T05:00:00Z"), utc("2022-12-10T13:00:00Z"))
+            .is_ok());
+
+        // without opening hours the resource is always open
+        let room = Resource::new("room-1", "Room 1", "room");
+        assert!(room
+            .check_open(utc("2022-12-10T05:00:00Z"), utc("2022-12-11T05:00:00Z"))
+            .is_ok());
+    }
+
+    #[test]
+    fn closed_intervals_should_fill_the_gaps() {
+        let lab = make_lab();
+        let closed = lab
+            .closed_intervals(utc("2022-12-05T00:00:00Z"), utc("2022-12-06T12:00:00Z"))
+            .unwrap();
+        assert_eq!(
+            closed,
+            vec![
+                (utc("2022-12-05T00:00:00Z"), utc("2022-12-05T17:00:00Z")),
+                (utc("2022-12-06T01:00:00Z"), utc("2022-12-06T12:00:00Z")),
+            ]
+        );
+    }
+}
@@ -23,14 +23,77 @@ macro_rules! impl_new {
     };
 }
 
-impl_new!(ReserveRequest => reservation, Reservation);
 impl_new!(FilterRequest => filter, ReservationFilter);
 impl_new!(QueryRequest => query, ReservationQuery);
 
-impl_new!(ConfirmRequest, GetRequest, CancelRequest);
+impl_new!(GetRequest);
+
+impl ReserveRequest {
+    pub fn new(rsvp: Reservation) -> Self {
+        Self {
+            reservation: Some(rsvp),
+            idempotency_key: String::new(),
+            group_id: String::new(),
+        }
+    }
+
+    pub fn with_idempotency_key(mut self, key: impl Into<String>) -> Self {
+        self.idempotency_key = key.into();
+        self
+    }
+
+    /// reserve any free member of the group instead of the resource of the reservation
+    pub fn with_group(mut self, group_id: impl Into<String>) -> Self {
+        self.group_id = group_id.into();
+        self
+    }
+}
+
+impl ConfirmRequest {
+    pub fn new(id: i64) -> Self {
+        Self {
+            id,
+            expected_version: 0,
+        }
+    }
+
+    pub fn with_expected_version(mut self, version: i64) -> Self {
+        self.expected_version = version;
+        self
+    }
+}
+
+impl CancelRequest {
+    pub fn new(id: i64) -> Self {
+        Self {
+            id,
+            reason: String::new(),
+            expected_version: 0,
+        }
+    }
+
+    pub fn with_reason(mut self, reason: impl Into<String>) -> Self {
+        self.reason = reason.into();
+        self
+    }
+
+    pub fn with_expected_version(mut self, version: i64) -> Self {
+        self.expected_version = version;
+        self
+    }
+}
 
 impl UpdateRequest {
     pub fn new(id: i64, note: String) -> Self {
-        Self { id, note }
+        Self {
+            id,
+            note,
+            expected_version: 0,
+        }
+    }
+
+    pub fn with_expected_version(mut self, version: i64) -> Self {
+        self.expected_version = version;
+        self
     }
 }
@@ -1,6 +1,7 @@
 use chrono::{DateTime, FixedOffset, Utc};
 use sqlx::{
     postgres::{types::PgRange, PgRow},
+    types::Uuid,
     FromRow, Row,
 };
 use std::ops::Bound;
@@ -27,6 +28,12 @@ impl Reservation {
             start: Some(convert_to_timestamp(&start.with_timezone(&Utc))),
             end: Some(convert_to_timestamp(&end.with_timezone(&Utc))),
             note: note.into(),
+            cancelled_at: None,
+            cancel_reason: String::new(),
+            hold_ttl_secs: 0,
+            expires_at: None,
+            series_id: String::new(),
+            version: 0,
         }
     }
 
@@ -50,6 +57,9 @@ impl Validator for Reservation {
             return Err(Error::InvalidResourceId(self.resource_id.clone()));
         }
         validate_range(self.start.as_ref(), self.end.as_ref())?;
+        if self.hold_ttl_secs < 0 {
+            return Err(Error::InvalidDuration(self.hold_ttl_secs));
+        }
         Ok(())
     }
 }
@@ -65,6 +75,12 @@ impl FromRow<'_, PgRow> for Reservation {
         let start = range.start.unwrap();
         let end = range.end.unwrap();
         let status: RsvpStatus = row.get("status");
+        let cancelled_at: Option<DateTime<Utc>> = row.get("cancelled_at");
+        let cancel_reason: Option<String> = row.get("cancel_reason");
+        let expires_at: Option<DateTime<Utc>> = row.get("expires_at");
+        let series_id: Option<Uuid> = row.get("series_id");
+        // changes recorded before versions were added have no version
+        let version: Option<i64> = row.get("version");
         Ok(Self {
             id,
             user_id: row.get("user_id"),
@@ -73,13 +89,19 @@ impl FromRow<'_, PgRow> for Reservation {
             start: Some(convert_to_timestamp(&start)),
             end: Some(convert_to_timestamp(&end)),
             note: row.get("note"),
+            cancelled_at: cancelled_at.as_ref().map(convert_to_timestamp),
+            cancel_reason: cancel_reason.unwrap_or_default(),
+            hold_ttl_secs: 0,
+            expires_at: expires_at.as_ref().map(convert_to_timestamp),
+            series_id: series_id.map(|id| id.to_string()).unwrap_or_default(),
+            version: version.unwrap_or_default(),
         })
     }
 }
 
-struct NaiveRange<T> {
-    start: Option<T>,
-    end: Option<T>,
+pub(super) struct NaiveRange<T> {
+    pub(super) start: Option<T>,
+    pub(super) end: Option<T>,
 }
 
 impl<T> From<PgRange<T>> for NaiveRange<T> {
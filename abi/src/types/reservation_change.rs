@@ -0,0 +1,39 @@
+use sqlx::{postgres::PgRow, FromRow, Row};
+
+use crate::{pager::Id, Reservation, ReservationChange, ReservationUpdateType, RsvpUpdateType};
+
+impl Id for ReservationChange {
+    fn id(&self) -> i64 {
+        self.id
+    }
+}
+
+impl ReservationChange {
+    /// collect rows of (change_id, op, side, reservation columns) into changes. Rows of the same
+    /// change must be adjacent, the order of the changes is kept
+    pub fn from_rows(rows: &[PgRow]) -> Result<Vec<Self>, sqlx::Error> {
+        let mut changes: Vec<Self> = Vec::new();
+        for row in rows {
+            let id: i64 = row.get("change_id");
+            if changes.last().map(|c| c.id) != Some(id) {
+                let op: RsvpUpdateType = row.get("op");
+                changes.push(Self {
+                    id,
+                    op: ReservationUpdateType::from(op) as i32,
+                    old: None,
+                    new: None,
+                });
+            }
+
+            let change = changes.last_mut().unwrap();
+            let rsvp = Some(Reservation::from_row(row)?);
+            let side: String = row.get("side");
+            if side == "old" {
+                change.old = rsvp;
+            } else {
+                change.new = rsvp;
+            }
+        }
+        Ok(changes)
+    }
+}
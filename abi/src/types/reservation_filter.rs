@@ -1,5 +1,10 @@
 use std::collections::VecDeque;
 
+use sqlx::{Postgres, QueryBuilder};
+
+use super::{
+    normalize_statuses, push_group_cond, push_user_resource_cond, status_cond, validate_statuses,
+};
 use crate::{
     pager::{Id, PageInfo, Pager, Paginator},
     Error, FilterPager, Normalizer, ReservationFilter, ReservationFilterBuilder, ReservationStatus,
@@ -28,7 +33,7 @@ impl Validator for ReservationFilter {
             }
         }
 
-        ReservationStatus::from_i32(self.status).ok_or(Error::InvalidStatus(self.status))?;
+        validate_statuses(&self.statuses)?;
 
         Ok(())
     }
@@ -36,9 +41,7 @@ impl Validator for ReservationFilter {
 
 impl Normalizer for ReservationFilter {
     fn do_normalize(&mut self) {
-        if self.status == ReservationStatus::Unknown as i32 {
-            self.status = ReservationStatus::Pending as i32;
-        }
+        normalize_statuses(&mut self.statuses);
     }
 }
 
@@ -67,8 +70,12 @@ impl ReservationFilter {
         self.cursor.unwrap_or(if self.desc { i64::MAX } else { 0 })
     }
 
-    pub fn get_status(&self) -> ReservationStatus {
-        ReservationStatus::from_i32(self.status).unwrap()
+    /// statuses to filter, empty if all statuses are wanted
+    pub fn get_statuses(&self) -> Vec<ReservationStatus> {
+        self.statuses
+            .iter()
+            .map(|s| ReservationStatus::from_i32(*s).unwrap())
+            .collect()
     }
 
     pub fn get_pager<T: Id>(&self, data: &mut VecDeque<T>) -> FilterPager {
@@ -85,10 +92,11 @@ impl ReservationFilter {
         page_info.map(|page_info| Self {
             resource_id: self.resource_id.clone(),
             user_id: self.user_id.clone(),
-            status: self.status,
+            statuses: self.statuses.clone(),
             cursor: page_info.cursor,
             page_size: page_info.page_size,
             desc: page_info.desc,
+            group_id: self.group_id.clone(),
         })
     }
 
@@ -102,29 +110,25 @@ impl ReservationFilter {
 }
 
 impl ToSql for ReservationFilter {
-    fn to_sql(&self) -> String {
+    fn to_sql(&self) -> QueryBuilder<'_, Postgres> {
         let middle_plus = if self.cursor.is_none() { 0 } else { 1 };
         let limit = self.page_size + 1 + middle_plus;
-        let status = self.get_status();
         let cursor_cond = if self.desc {
             format!("id <= {}", self.get_cursor())
         } else {
             format!("id >= {}", self.get_cursor())
         };
 
-        let user_resource_cond = match (self.user_id.is_empty(), self.resource_id.is_empty()) {
-            (true, true) => "TRUE".into(),
-            (true, false) => format!("resource_id = '{}'", self.resource_id),
-            (false, true) => format!("user_id = '{}'", self.user_id),
-            (false, false) => format!(
-                "user_id = '{}' AND resource_id = '{}'",
-                self.user_id, self.resource_id
-            ),
-        };
-
         let direction = if self.desc { "DESC" } else { "ASC" };
-        format!("SELECT * FROM rsvp.reservations WHERE status = '{}'::rsvp.reservation_status AND {} AND {} ORDER BY id {} LIMIT {}",
-             status, cursor_cond, user_resource_cond, direction, limit)
+        let mut builder = QueryBuilder::new(format!(
+            "SELECT * FROM rsvp.reservations WHERE {} AND {} AND ",
+            status_cond(&self.statuses),
+            cursor_cond
+        ));
+        push_user_resource_cond(&mut builder, &self.user_id, &self.resource_id);
+        push_group_cond(&mut builder, &self.group_id);
+        builder.push(format!(" ORDER BY id {} LIMIT {}", direction, limit));
+        builder
     }
 }
 
@@ -141,8 +145,8 @@ mod tests {
             .build()
             .unwrap();
 
-        let sql = filter.to_sql();
-        assert_eq!(sql, "SELECT * FROM rsvp.reservations WHERE status = 'pending'::rsvp.reservation_status AND id >= 0 AND user_id = 'user_id' ORDER BY id ASC LIMIT 11");
+        let sql = filter.to_sql().into_sql();
+        assert_eq!(sql, "SELECT * FROM rsvp.reservations WHERE TRUE AND id >= 0 AND user_id = $1 ORDER BY id ASC LIMIT 11");
 
         let filter = ReservationFilterBuilder::default()
             .user_id("user_id")
@@ -150,16 +154,24 @@ mod tests {
             .build()
             .unwrap();
 
-        let sql = filter.to_sql();
-        assert_eq!(sql, "SELECT * FROM rsvp.reservations WHERE status = 'pending'::rsvp.reservation_status AND id >= 0 AND user_id = 'user_id' AND resource_id = 'resource_id' ORDER BY id ASC LIMIT 11");
+        let sql = filter.to_sql().into_sql();
+        assert_eq!(sql, "SELECT * FROM rsvp.reservations WHERE TRUE AND id >= 0 AND user_id = $1 AND resource_id = $2 ORDER BY id ASC LIMIT 11");
+
+        let filter = ReservationFilterBuilder::default()
+            .group_id("floor-3")
+            .build()
+            .unwrap();
+
+        let sql = filter.to_sql().into_sql();
+        assert_eq!(sql, "SELECT * FROM rsvp.reservations WHERE TRUE AND id >= 0 AND TRUE AND resource_id IN (SELECT rsvp.resource_members($1)) ORDER BY id ASC LIMIT 11");
 
         let filter = ReservationFilterBuilder::default()
             .desc(true)
             .build()
             .unwrap();
 
-        let sql = filter.to_sql();
-        assert_eq!(sql, "SELECT * FROM rsvp.reservations WHERE status = 'pending'::rsvp.reservation_status AND id <= 9223372036854775807 AND TRUE ORDER BY id DESC LIMIT 11");
+        let sql = filter.to_sql().into_sql();
+        assert_eq!(sql, "SELECT * FROM rsvp.reservations WHERE TRUE AND id <= 9223372036854775807 AND TRUE ORDER BY id DESC LIMIT 11");
 
         let filter = ReservationFilterBuilder::default()
             .user_id("user_id")
@@ -167,8 +179,8 @@ mod tests {
             .build()
             .unwrap();
 
-        let sql = filter.to_sql();
-        assert_eq!(sql, "SELECT * FROM rsvp.reservations WHERE status = 'pending'::rsvp.reservation_status AND id >= 100 AND user_id = 'user_id' ORDER BY id ASC LIMIT 12");
+        let sql = filter.to_sql().into_sql();
+        assert_eq!(sql, "SELECT * FROM rsvp.reservations WHERE TRUE AND id >= 100 AND user_id = $1 ORDER BY id ASC LIMIT 12");
 
         let filter = ReservationFilterBuilder::default()
             .user_id("user_id")
@@ -177,8 +189,19 @@ mod tests {
             .build()
             .unwrap();
 
-        let sql = filter.to_sql();
-        assert_eq!(sql, "SELECT * FROM rsvp.reservations WHERE status = 'pending'::rsvp.reservation_status AND id <= 10 AND user_id = 'user_id' ORDER BY id DESC LIMIT 12");
+        let sql = filter.to_sql().into_sql();
+        assert_eq!(sql, "SELECT * FROM rsvp.reservations WHERE TRUE AND id <= 10 AND user_id = $1 ORDER BY id DESC LIMIT 12");
+    }
+
+    #[test]
+    fn filter_should_bind_ids_instead_of_splicing_them() {
+        let filter = ReservationFilterBuilder::default()
+            .resource_id("room'; DROP TABLE rsvp.reservations; --")
+            .build()
+            .unwrap();
+
+        let sql = filter.to_sql().into_sql();
+        assert_eq!(sql, "SELECT * FROM rsvp.reservations WHERE TRUE AND id >= 0 AND resource_id = $1 ORDER BY id ASC LIMIT 11");
     }
 
     #[test]
@@ -193,8 +216,8 @@ mod tests {
         assert_eq!(pager.prev, None);
         assert_eq!(pager.next, Some(10));
 
-        let sql = filter.to_sql();
-        assert_eq!(sql, "SELECT * FROM rsvp.reservations WHERE status = 'pending'::rsvp.reservation_status AND id >= 0 AND resource_id = 'router-1' ORDER BY id ASC LIMIT 11");
+        let sql = filter.to_sql().into_sql();
+        assert_eq!(sql, "SELECT * FROM rsvp.reservations WHERE TRUE AND id >= 0 AND resource_id = $1 ORDER BY id ASC LIMIT 11");
 
         let filter = filter.next_page(&pager).unwrap();
         let mut data = pager_test_utils::generate_test_ids(10, 21);
@@ -202,8 +225,8 @@ mod tests {
         assert_eq!(pager.prev, Some(11));
         assert_eq!(pager.next, Some(20));
 
-        let sql = filter.to_sql();
-        assert_eq!(sql, "SELECT * FROM rsvp.reservations WHERE status = 'pending'::rsvp.reservation_status AND id >= 10 AND resource_id = 'router-1' ORDER BY id ASC LIMIT 12");
+        let sql = filter.to_sql().into_sql();
+        assert_eq!(sql, "SELECT * FROM rsvp.reservations WHERE TRUE AND id >= 10 AND resource_id = $1 ORDER BY id ASC LIMIT 12");
 
         let filter = filter.next_page(&pager).unwrap();
         let mut data = pager_test_utils::generate_test_ids(20, 25);
@@ -211,7 +234,7 @@ mod tests {
         assert_eq!(pager.prev, Some(21));
         assert!(pager.next.is_none());
 
-        let sql = filter.to_sql();
-        assert_eq!(sql, "SELECT * FROM rsvp.reservations WHERE status = 'pending'::rsvp.reservation_status AND id >= 20 AND resource_id = 'router-1' ORDER BY id ASC LIMIT 12");
+        let sql = filter.to_sql().into_sql();
+        assert_eq!(sql, "SELECT * FROM rsvp.reservations WHERE TRUE AND id >= 20 AND resource_id = $1 ORDER BY id ASC LIMIT 12");
     }
 }
@@ -1,8 +1,12 @@
 use prost_types::Timestamp;
+use sqlx::{Postgres, QueryBuilder};
 
+use super::{
+    normalize_statuses, push_group_cond, push_user_resource_cond, status_cond, validate_statuses,
+};
 use crate::{
     convert_to_utc_time, Error, Normalizer, ReservationQuery, ReservationQueryBuilder,
-    ReservationStatus, ToSql, Validator,
+    ReservationQueryMode, ReservationStatus, ToSql, Validator,
 };
 
 impl ReservationQueryBuilder {
@@ -16,14 +20,23 @@ impl ReservationQueryBuilder {
 }
 
 impl ReservationQuery {
-    pub fn get_status(&self) -> ReservationStatus {
-        ReservationStatus::from_i32(self.status).unwrap()
+    /// statuses to query, empty if all statuses are wanted
+    pub fn get_statuses(&self) -> Vec<ReservationStatus> {
+        self.statuses
+            .iter()
+            .map(|s| ReservationStatus::from_i32(*s).unwrap())
+            .collect()
+    }
+
+    pub fn get_mode(&self) -> ReservationQueryMode {
+        ReservationQueryMode::from_i32(self.mode).unwrap()
     }
 }
 
 impl Validator for ReservationQuery {
     fn validate(&self) -> Result<(), Error> {
-        ReservationStatus::from_i32(self.status).ok_or(Error::InvalidStatus(self.status))?;
+        validate_statuses(&self.statuses)?;
+        ReservationQueryMode::from_i32(self.mode).ok_or(Error::InvalidQueryMode(self.mode))?;
 
         if let (Some(start), Some(end)) = (self.start.as_ref(), self.end.as_ref()) {
             if start.seconds >= end.seconds {
@@ -37,34 +50,35 @@ impl Validator for ReservationQuery {
 
 impl Normalizer for ReservationQuery {
     fn do_normalize(&mut self) {
-        if self.get_status() == ReservationStatus::Unknown {
-            self.status = ReservationStatus::Pending as i32
-        }
+        normalize_statuses(&mut self.statuses);
     }
 }
 
 impl ToSql for ReservationQuery {
-    fn to_sql(&self) -> String {
-        let status = self.get_status();
+    fn to_sql(&self) -> QueryBuilder<'_, Postgres> {
         let timespan = format!(
             "tstzrange('{}', '{}')",
             get_time_string(self.start.as_ref(), true),
             get_time_string(self.end.as_ref(), false)
         );
 
-        let condition = match (self.user_id.is_empty(), self.resource_id.is_empty()) {
-            (true, true) => "TRUE".into(),
-            (true, false) => format!("resource_id = '{}'", self.resource_id),
-            (false, true) => format!("user_id = '{}'", self.user_id),
-            (false, false) => format!(
-                "user_id = '{}' AND resource_id = '{}'",
-                self.user_id, self.resource_id
-            ),
+        let timespan_cond = match self.get_mode() {
+            ReservationQueryMode::Contained => format!("{} @> timespan", timespan),
+            ReservationQueryMode::Overlapping => format!("{} && timespan", timespan),
+            ReservationQueryMode::StartingWithin => format!("{} @> lower(timespan)", timespan),
         };
 
         let direction = if self.desc { "DESC" } else { "ASC" };
 
-        format!("SELECT * FROM rsvp.reservations WHERE {} @> timespan AND status = '{}'::rsvp.reservation_status AND {} ORDER BY lower(timespan) {}", timespan, status, condition, direction)
+        let mut builder = QueryBuilder::new(format!(
+            "SELECT * FROM rsvp.reservations WHERE {} AND {} AND ",
+            timespan_cond,
+            status_cond(&self.statuses)
+        ));
+        push_user_resource_cond(&mut builder, &self.user_id, &self.resource_id);
+        push_group_cond(&mut builder, &self.group_id);
+        builder.push(format!(" ORDER BY lower(timespan) {}", direction));
+        builder
     }
 }
 
@@ -86,9 +100,9 @@ mod tests {
             .build()
             .unwrap();
 
-        let sql = query.to_sql();
+        let sql = query.to_sql().into_sql();
 
-        assert_eq!(sql, "SELECT * FROM rsvp.reservations WHERE tstzrange('-infinity', 'infinity') @> timespan AND status = 'pending'::rsvp.reservation_status AND user_id = 'user_id' ORDER BY lower(timespan) ASC");
+        assert_eq!(sql, "SELECT * FROM rsvp.reservations WHERE tstzrange('-infinity', 'infinity') @> timespan AND TRUE AND user_id = $1 ORDER BY lower(timespan) ASC");
 
         let query = ReservationQueryBuilder::default()
             .resource_id("test")
@@ -96,15 +110,55 @@ mod tests {
             .build()
             .unwrap();
 
-        let sql = query.to_sql();
-        assert_eq!(sql, "SELECT * FROM rsvp.reservations WHERE tstzrange('2021-11-01T22:00:00+00:00', 'infinity') @> timespan AND status = 'pending'::rsvp.reservation_status AND resource_id = 'test' ORDER BY lower(timespan) ASC");
+        let sql = query.to_sql().into_sql();
+        assert_eq!(sql, "SELECT * FROM rsvp.reservations WHERE tstzrange('2021-11-01T22:00:00+00:00', 'infinity') @> timespan AND TRUE AND resource_id = $1 ORDER BY lower(timespan) ASC");
+
+        let query = ReservationQueryBuilder::default()
+            .user_id("user_id")
+            .group_id("floor-3")
+            .build()
+            .unwrap();
+
+        let sql = query.to_sql().into_sql();
+        assert_eq!(sql, "SELECT * FROM rsvp.reservations WHERE tstzrange('-infinity', 'infinity') @> timespan AND TRUE AND user_id = $1 AND resource_id IN (SELECT rsvp.resource_members($2)) ORDER BY lower(timespan) ASC");
 
         let query = ReservationQueryBuilder::default()
             .end("2021-11-01T16:00:00-0700".parse::<Timestamp>().unwrap())
             .build()
             .unwrap();
 
-        let sql = query.to_sql();
-        assert_eq!(sql, "SELECT * FROM rsvp.reservations WHERE tstzrange('-infinity', '2021-11-01T23:00:00+00:00') @> timespan AND status = 'pending'::rsvp.reservation_status AND TRUE ORDER BY lower(timespan) ASC");
+        let sql = query.to_sql().into_sql();
+        assert_eq!(sql, "SELECT * FROM rsvp.reservations WHERE tstzrange('-infinity', '2021-11-01T23:00:00+00:00') @> timespan AND TRUE AND TRUE ORDER BY lower(timespan) ASC");
+    }
+
+    #[test]
+    fn query_should_generate_sql_for_each_mode() {
+        let builder = ReservationQueryBuilder::default()
+            .start("2021-11-01T00:00:00Z".parse::<Timestamp>().unwrap())
+            .end("2021-11-02T00:00:00Z".parse::<Timestamp>().unwrap())
+            .clone();
+
+        let query = builder.clone().build().unwrap();
+        let sql = query.to_sql().into_sql();
+        assert_eq!(sql, "SELECT * FROM rsvp.reservations WHERE tstzrange('2021-11-01T00:00:00+00:00', '2021-11-02T00:00:00+00:00') @> timespan AND TRUE AND TRUE ORDER BY lower(timespan) ASC");
+
+        let query = builder
+            .clone()
+            .mode(ReservationQueryMode::Overlapping)
+            .build()
+            .unwrap();
+        let sql = query.to_sql().into_sql();
+        assert_eq!(sql, "SELECT * FROM rsvp.reservations WHERE tstzrange('2021-11-01T00:00:00+00:00', '2021-11-02T00:00:00+00:00') && timespan AND TRUE AND TRUE ORDER BY lower(timespan) ASC");
+
+        let query = builder
+            .clone()
+            .mode(ReservationQueryMode::StartingWithin)
+            .build()
+            .unwrap();
+        let sql = query.to_sql().into_sql();
+        assert_eq!(sql, "SELECT * FROM rsvp.reservations WHERE tstzrange('2021-11-01T00:00:00+00:00', '2021-11-02T00:00:00+00:00') @> lower(timespan) AND TRUE AND TRUE ORDER BY lower(timespan) ASC");
+
+        let err = builder.clone().mode(10).build().unwrap_err();
+        assert_eq!(err, Error::InvalidQueryMode(10));
     }
 }
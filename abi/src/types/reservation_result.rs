@@ -0,0 +1,74 @@
+use prost::Message;
+use tonic::Status;
+
+use crate::{Error, Reservation, ReservationId, ReservationResult, RpcStatus};
+
+impl ReservationResult {
+    pub fn new(id: ReservationId, result: Result<Reservation, Error>) -> Self {
+        match result {
+            Ok(rsvp) => Self {
+                id,
+                reservation: Some(rsvp),
+                error: None,
+            },
+            Err(e) => Self {
+                id,
+                reservation: None,
+                error: Some(Status::from(e).into()),
+            },
+        }
+    }
+}
+
+impl From<Status> for RpcStatus {
+    /// details of the status are kept if they are a google.rpc.Status, e.g. the conflict details
+    fn from(status: Status) -> Self {
+        let details = RpcStatus::decode(status.details())
+            .map(|s| s.details)
+            .unwrap_or_default();
+        Self {
+            code: status.code() as i32,
+            message: status.message().to_string(),
+            details,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConflictDetail, ReservationConflictInfo, ReservationStatus};
+    use tonic::Code;
+
+    #[test]
+    fn reservation_result_should_carry_the_error_status() {
+        let result = ReservationResult::new(
+            1,
+            Err(Error::IllegalTransition(
+                ReservationStatus::Confirmed,
+                ReservationStatus::Confirmed,
+            )),
+        );
+        assert_eq!(result.reservation, None);
+        let error = result.error.unwrap();
+        assert_eq!(error.code, Code::FailedPrecondition as i32);
+        assert_eq!(
+            error.message,
+            "Illegal status transition from confirmed to confirmed"
+        );
+
+        let result = ReservationResult::new(
+            2,
+            Err(Error::ConflictReservation(
+                ReservationConflictInfo::Unparsed("oops".into()),
+            )),
+        );
+        let error = result.error.unwrap();
+        assert_eq!(error.details.len(), 1);
+        let detail = ConflictDetail::decode(error.details[0].value.as_slice()).unwrap();
+        assert_eq!(
+            detail,
+            (&ReservationConflictInfo::Unparsed("oops".into())).into()
+        );
+    }
+}
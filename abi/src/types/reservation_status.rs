@@ -8,18 +8,71 @@ impl fmt::Display for ReservationStatus {
             ReservationStatus::Pending => write!(f, "pending"),
             ReservationStatus::Blocked => write!(f, "blocked"),
             ReservationStatus::Confirmed => write!(f, "confirmed"),
+            ReservationStatus::Cancelled => write!(f, "cancelled"),
             ReservationStatus::Unknown => write!(f, "unknown"),
         }
     }
 }
 
+impl ReservationStatus {
+    /// allowed status transitions as (from, to)
+    pub const TRANSITIONS: &'static [(ReservationStatus, ReservationStatus)] = &[
+        // confirm
+        (ReservationStatus::Pending, ReservationStatus::Confirmed),
+        // unconfirm
+        (ReservationStatus::Confirmed, ReservationStatus::Pending),
+        // block
+        (ReservationStatus::Pending, ReservationStatus::Blocked),
+        // release
+        (ReservationStatus::Blocked, ReservationStatus::Pending),
+        // cancel, cancelled is final
+        (ReservationStatus::Pending, ReservationStatus::Cancelled),
+        (ReservationStatus::Confirmed, ReservationStatus::Cancelled),
+        (ReservationStatus::Blocked, ReservationStatus::Cancelled),
+    ];
+
+    pub fn can_transition_to(&self, to: ReservationStatus) -> bool {
+        Self::TRANSITIONS.contains(&(*self, to))
+    }
+
+    /// statuses that are allowed to transition to this status
+    pub fn transition_sources(&self) -> Vec<ReservationStatus> {
+        Self::TRANSITIONS
+            .iter()
+            .filter(|(_, to)| to == self)
+            .map(|(from, _)| *from)
+            .collect()
+    }
+}
+
 impl From<RsvpStatus> for ReservationStatus {
     fn from(status: RsvpStatus) -> Self {
         match status {
             RsvpStatus::Pending => ReservationStatus::Pending,
             RsvpStatus::Blocked => ReservationStatus::Blocked,
             RsvpStatus::Confirmed => ReservationStatus::Confirmed,
+            RsvpStatus::Cancelled => ReservationStatus::Cancelled,
             RsvpStatus::Unknown => ReservationStatus::Unknown,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transitions_should_follow_the_table() {
+        assert!(ReservationStatus::Pending.can_transition_to(ReservationStatus::Confirmed));
+        assert!(ReservationStatus::Confirmed.can_transition_to(ReservationStatus::Pending));
+        assert!(!ReservationStatus::Confirmed.can_transition_to(ReservationStatus::Confirmed));
+        assert!(!ReservationStatus::Blocked.can_transition_to(ReservationStatus::Confirmed));
+
+        assert_eq!(
+            ReservationStatus::Pending.transition_sources(),
+            vec![ReservationStatus::Confirmed, ReservationStatus::Blocked]
+        );
+        assert!(ReservationStatus::Unknown.transition_sources().is_empty());
+        assert!(!ReservationStatus::Cancelled.can_transition_to(ReservationStatus::Pending));
+    }
+}
@@ -0,0 +1,206 @@
+use chrono::{Duration, Utc};
+use chrono_tz::Tz;
+use rrule::RRuleSet;
+
+use crate::{
+    convert_to_timestamp, convert_to_utc_time, Error, RecurrenceMode, Reservation,
+    ReserveRecurringRequest, Validator,
+};
+
+/// max number of occurrences a recurring reservation could expand to
+const MAX_OCCURRENCES: u16 = 366;
+
+impl Validator for ReserveRecurringRequest {
+    fn validate(&self) -> Result<(), Error> {
+        let Some(rsvp) = self.reservation.as_ref() else {
+            return Err(Error::InvalidRecurrence("missing reservation".into()));
+        };
+        rsvp.validate()?;
+
+        RecurrenceMode::from_i32(self.mode)
+            .ok_or_else(|| Error::InvalidRecurrence(format!("invalid mode: {}", self.mode)))?;
+        self.tz()?;
+
+        Ok(())
+    }
+}
+
+impl ReserveRecurringRequest {
+    /// timezone the rule is expanded in, empty is UTC
+    pub fn tz(&self) -> Result<Tz, Error> {
+        if self.timezone.is_empty() {
+            return Ok(Tz::UTC);
+        }
+        self.timezone
+            .parse()
+            .map_err(|_| Error::InvalidTimezone(self.timezone.clone()))
+    }
+
+    /// expand the template reservation into its occurrences ordered by start time,
+    /// occurrences starting at one of the exdates are skipped. The rule is evaluated in the
+    /// local time of the timezone
+    pub fn occurrences(&self) -> Result<Vec<Reservation>, Error> {
+        self.validate()?;
+
+        let template = self.reservation.as_ref().unwrap();
+        let start = convert_to_utc_time(template.start.as_ref().unwrap());
+        let end = convert_to_utc_time(template.end.as_ref().unwrap());
+        let tz = self.tz()?;
+        let rrule = self.rrule.trim().trim_start_matches("RRULE:");
+        let rrule_set: RRuleSet = format!(
+            "DTSTART;TZID={}:{}\nRRULE:{}",
+            tz.name(),
+            start.with_timezone(&tz).format("%Y%m%dT%H%M%S"),
+            rrule
+        )
+        .parse()
+        .map_err(|e: rrule::RRuleError| Error::InvalidRecurrence(e.to_string()))?;
+
+        let (starts, _) = rrule_set.all(MAX_OCCURRENCES + 1);
+        if starts.len() > MAX_OCCURRENCES as usize {
+            return Err(Error::InvalidRecurrence(format!(
+                "more than {} occurrences",
+                MAX_OCCURRENCES
+            )));
+        }
+
+        let exdates: Vec<_> = self.exdates.iter().map(convert_to_utc_time).collect();
+        let duration: Duration = end - start;
+        Ok(starts
+            .into_iter()
+            .map(|dt| dt.with_timezone(&Utc))
+            .filter(|dt| !exdates.contains(dt))
+            .map(|dt| Reservation {
+                start: Some(convert_to_timestamp(&dt)),
+                end: Some(convert_to_timestamp(&(dt + duration))),
+                ..template.clone()
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prost_types::Timestamp;
+
+    fn make_request(rrule: &str, exdates: &[&str]) -> ReserveRecurringRequest {
+        ReserveRecurringRequest {
+            reservation: Some(Reservation::new_pending(
+                "user_id_1",
+                "standup-room",
+                "2022-12-05T09:00:00-0800".parse().unwrap(),
+                "2022-12-05T09:30:00-0800".parse().unwrap(),
+                "weekly standup",
+            )),
+            rrule: rrule.into(),
+            exdates: exdates.iter().map(|s| s.parse().unwrap()).collect(),
+            mode: RecurrenceMode::AllOrNothing as i32,
+            timezone: String::new(),
+        }
+    }
+
+    fn starts(rsvps: &[Reservation]) -> Vec<Timestamp> {
+        rsvps.iter().map(|r| r.start.clone().unwrap()).collect()
+    }
+
+    #[test]
+    fn occurrences_should_follow_the_rrule() {
+        let request = make_request("FREQ=WEEKLY;BYDAY=MO,WE;COUNT=4", &[]);
+        let rsvps = request.occurrences().unwrap();
+        assert_eq!(
+            starts(&rsvps),
+            [
+                "2022-12-05T17:00:00Z",
+                "2022-12-07T17:00:00Z",
+                "2022-12-12T17:00:00Z",
+                "2022-12-14T17:00:00Z",
+            ]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect::<Vec<Timestamp>>()
+        );
+        assert_eq!(rsvps[3].end, Some("2022-12-14T17:30:00Z".parse().unwrap()));
+        assert!(rsvps.iter().all(|r| r.note == "weekly standup"));
+    }
+
+    #[test]
+    fn occurrences_should_keep_local_time_in_timezone() {
+        // 09:00 PST, daylight saving time starts on 2023-03-12
+        let mut request = make_request("FREQ=WEEKLY;COUNT=2", &[]);
+        let template = request.reservation.as_mut().unwrap();
+        template.start = Some("2023-03-06T17:00:00Z".parse().unwrap());
+        template.end = Some("2023-03-06T17:30:00Z".parse().unwrap());
+        let utc = request.occurrences().unwrap();
+        assert_eq!(utc[1].start, Some("2023-03-13T17:00:00Z".parse().unwrap()));
+
+        request.timezone = "America/Los_Angeles".into();
+        let local = request.occurrences().unwrap();
+        assert_eq!(
+            starts(&local),
+            vec![
+                "2023-03-06T17:00:00Z".parse().unwrap(),
+                "2023-03-13T16:00:00Z".parse().unwrap(),
+            ]
+        );
+        assert_eq!(local[1].end, Some("2023-03-13T16:30:00Z".parse().unwrap()));
+
+        // Monday 20:00 PST is already Tuesday in UTC
+        let mut request = make_request("FREQ=WEEKLY;BYDAY=MO;COUNT=2", &[]);
+        request.timezone = "America/Los_Angeles".into();
+        let template = request.reservation.as_mut().unwrap();
+        template.start = Some("2022-12-06T04:00:00Z".parse().unwrap());
+        template.end = Some("2022-12-06T05:00:00Z".parse().unwrap());
+        assert_eq!(
+            starts(&request.occurrences().unwrap()),
+            vec![
+                "2022-12-06T04:00:00Z".parse().unwrap(),
+                "2022-12-13T04:00:00Z".parse().unwrap(),
+            ]
+        );
+
+        request.timezone = "Mars/Olympus_Mons".into();
+        assert_eq!(
+            request.occurrences(),
+            Err(Error::InvalidTimezone("Mars/Olympus_Mons".into()))
+        );
+    }
+
+    #[test]
+    fn occurrences_should_skip_exdates() {
+        let request = make_request("RRULE:FREQ=DAILY;COUNT=3", &["2022-12-06T17:00:00Z"]);
+        let rsvps = request.occurrences().unwrap();
+        assert_eq!(
+            starts(&rsvps),
+            vec![
+                "2022-12-05T17:00:00Z".parse().unwrap(),
+                "2022-12-07T17:00:00Z".parse().unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_recurrence_should_be_rejected() {
+        let request = make_request("FREQ=SOMETIMES", &[]);
+        assert!(matches!(
+            request.occurrences(),
+            Err(Error::InvalidRecurrence(_))
+        ));
+
+        // unbounded rules expand to too many occurrences
+        let request = make_request("FREQ=DAILY", &[]);
+        assert_eq!(
+            request.occurrences(),
+            Err(Error::InvalidRecurrence("more than 366 occurrences".into()))
+        );
+
+        let request = ReserveRecurringRequest {
+            mode: 10,
+            ..make_request("FREQ=DAILY;COUNT=3", &[])
+        };
+        assert_eq!(
+            request.occurrences(),
+            Err(Error::InvalidRecurrence("invalid mode: 10".into()))
+        );
+    }
+}
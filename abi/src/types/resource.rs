@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+
+use sqlx::{postgres::PgRow, types::Json, FromRow, Postgres, QueryBuilder, Row};
+
+use crate::{Error, ListResourcesRequest, OpeningHours, Resource, ToSql, Validator};
+
+/// max length of the resource id, same as the resource_id column of reservations
+const MAX_RESOURCE_ID_LEN: usize = 64;
+
+impl Resource {
+    /// a new active resource that could be reserved once at a time
+    pub fn new(
+        id: impl Into<String>,
+        name: impl Into<String>,
+        resource_type: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            resource_type: resource_type.into(),
+            metadata: HashMap::new(),
+            active: true,
+            capacity: 1,
+            parent_id: String::new(),
+            timezone: String::new(),
+            opening_hours: Vec::new(),
+        }
+    }
+
+    /// make the resource a member of the group
+    pub fn with_parent(mut self, parent_id: impl Into<String>) -> Self {
+        self.parent_id = parent_id.into();
+        self
+    }
+
+    /// allow up to `capacity` overlapping reservations
+    pub fn with_capacity(mut self, capacity: i32) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// only allow reservations within the weekly opening hours, in the local time of `timezone`
+    pub fn with_opening_hours(
+        mut self,
+        timezone: impl Into<String>,
+        opening_hours: impl IntoIterator<Item = OpeningHours>,
+    ) -> Self {
+        self.timezone = timezone.into();
+        self.opening_hours = opening_hours.into_iter().collect();
+        self
+    }
+
+    pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+}
+
+impl Validator for Resource {
+    fn validate(&self) -> Result<(), Error> {
+        if self.id.is_empty() || self.id.len() > MAX_RESOURCE_ID_LEN {
+            return Err(Error::InvalidResourceId(self.id.clone()));
+        }
+        if self.parent_id == self.id || self.parent_id.len() > MAX_RESOURCE_ID_LEN {
+            return Err(Error::InvalidParent(self.parent_id.clone()));
+        }
+        if self.capacity < 0 {
+            return Err(Error::InvalidCapacity(self.capacity));
+        }
+        self.tz()?;
+        for hours in self.opening_hours.iter() {
+            hours.validate()?;
+        }
+        Ok(())
+    }
+}
+
+impl FromRow<'_, PgRow> for Resource {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        let metadata: Json<HashMap<String, String>> = row.get("metadata");
+        let opening_hours: Json<Vec<OpeningHours>> = row.get("opening_hours");
+        Ok(Self {
+            id: row.get("id"),
+            name: row.get("name"),
+            resource_type: row.get("resource_type"),
+            metadata: metadata.0,
+            active: row.get("active"),
+            capacity: row.get("capacity"),
+            parent_id: row
+                .get::<Option<String>, _>("parent_id")
+                .unwrap_or_default(),
+            timezone: row.get("timezone"),
+            opening_hours: opening_hours.0,
+        })
+    }
+}
+
+impl ToSql for ListResourcesRequest {
+    fn to_sql(&self) -> QueryBuilder<'_, Postgres> {
+        let mut builder = QueryBuilder::new("SELECT * FROM rsvp.resources WHERE ");
+        if self.resource_type.is_empty() {
+            builder.push("TRUE");
+        } else {
+            builder
+                .push("resource_type = ")
+                .push_bind(&self.resource_type);
+        }
+        if !self.include_inactive {
+            builder.push(" AND active");
+        }
+        if !self.parent_id.is_empty() {
+            builder.push(" AND parent_id = ").push_bind(&self.parent_id);
+        }
+        builder.push(" ORDER BY id");
+        builder
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resource_should_validate_id() {
+        assert!(Resource::new("room-1", "Room 1", "room").validate().is_ok());
+        assert_eq!(
+            Resource::new("", "Room 1", "room").validate(),
+            Err(Error::InvalidResourceId("".into()))
+        );
+        let id = "r".repeat(65);
+        assert_eq!(
+            Resource::new(&id, "Room", "room").validate(),
+            Err(Error::InvalidResourceId(id))
+        );
+        assert_eq!(
+            Resource::new("desk-pool-1", "Desks", "desk")
+                .with_capacity(-1)
+                .validate(),
+            Err(Error::InvalidCapacity(-1))
+        );
+        assert_eq!(
+            Resource::new("floor-3", "Floor 3", "floor")
+                .with_parent("floor-3")
+                .validate(),
+            Err(Error::InvalidParent("floor-3".into()))
+        );
+    }
+
+    #[test]
+    fn list_resources_request_should_generate_correct_sql() {
+        let request = ListResourcesRequest::default();
+        assert_eq!(
+            request.to_sql().into_sql(),
+            "SELECT * FROM rsvp.resources WHERE TRUE AND active ORDER BY id"
+        );
+
+        let request = ListResourcesRequest {
+            resource_type: "room".into(),
+            include_inactive: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            request.to_sql().into_sql(),
+            "SELECT * FROM rsvp.resources WHERE resource_type = $1 ORDER BY id"
+        );
+
+        let request = ListResourcesRequest {
+            parent_id: "floor-3".into(),
+            ..Default::default()
+        };
+        assert_eq!(
+            request.to_sql().into_sql(),
+            "SELECT * FROM rsvp.resources WHERE TRUE AND active AND parent_id = $1 ORDER BY id"
+        );
+    }
+}
@@ -0,0 +1,97 @@
+use sqlx::{types::Uuid, Postgres, QueryBuilder};
+
+use crate::{Error, ReservationId, SeriesScope, SeriesSelector, ToSql, Validator};
+
+impl SeriesSelector {
+    pub fn new(series_id: impl Into<String>, id: ReservationId, scope: SeriesScope) -> Self {
+        Self {
+            series_id: series_id.into(),
+            id,
+            scope: scope as i32,
+        }
+    }
+}
+
+impl Validator for SeriesSelector {
+    fn validate(&self) -> Result<(), Error> {
+        if Uuid::parse_str(&self.series_id).is_err() {
+            return Err(Error::InvalidSeriesId(self.series_id.clone()));
+        }
+
+        let scope =
+            SeriesScope::from_i32(self.scope).ok_or(Error::InvalidSeriesScope(self.scope))?;
+        if scope != SeriesScope::All {
+            self.id.validate()?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ToSql for SeriesSelector {
+    /// ids of the selected occurrences order by start time, rows are locked for update
+    fn to_sql(&self) -> QueryBuilder<'_, Postgres> {
+        let mut builder = QueryBuilder::new("SELECT id FROM rsvp.reservations WHERE series_id = ");
+        builder
+            .push_bind(&self.series_id)
+            .push("::uuid AND status <> 'cancelled'");
+
+        match self.scope() {
+            SeriesScope::This => {
+                builder.push(" AND id = ").push_bind(self.id);
+            }
+            SeriesScope::ThisAndFollowing => {
+                builder
+                    .push(" AND lower(timespan) >= (SELECT lower(timespan) FROM rsvp.reservations WHERE id = ")
+                    .push_bind(self.id)
+                    .push(" AND series_id = ")
+                    .push_bind(&self.series_id)
+                    .push("::uuid)");
+            }
+            SeriesScope::All => {}
+        }
+
+        builder.push(" ORDER BY lower(timespan), id FOR UPDATE");
+        builder
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SERIES_ID: &str = "0f5ea9a4-8a3b-4c51-9d2a-3a1c6a5f2b10";
+
+    #[test]
+    fn series_selector_should_validate() {
+        let selector = SeriesSelector::new("not-a-uuid", 1, SeriesScope::This);
+        assert_eq!(
+            selector.validate(),
+            Err(Error::InvalidSeriesId("not-a-uuid".into()))
+        );
+
+        let selector = SeriesSelector::new(SERIES_ID, 0, SeriesScope::ThisAndFollowing);
+        assert_eq!(selector.validate(), Err(Error::InvalidReservationId(0)));
+
+        let selector = SeriesSelector::new(SERIES_ID, 0, SeriesScope::All);
+        assert!(selector.validate().is_ok());
+
+        let selector = SeriesSelector {
+            scope: 10,
+            ..SeriesSelector::new(SERIES_ID, 1, SeriesScope::This)
+        };
+        assert_eq!(selector.validate(), Err(Error::InvalidSeriesScope(10)));
+    }
+
+    #[test]
+    fn series_selector_should_generate_correct_sql() {
+        let selector = SeriesSelector::new(SERIES_ID, 1, SeriesScope::This);
+        assert_eq!(selector.to_sql().into_sql(), "SELECT id FROM rsvp.reservations WHERE series_id = $1::uuid AND status <> 'cancelled' AND id = $2 ORDER BY lower(timespan), id FOR UPDATE");
+
+        let selector = SeriesSelector::new(SERIES_ID, 1, SeriesScope::ThisAndFollowing);
+        assert_eq!(selector.to_sql().into_sql(), "SELECT id FROM rsvp.reservations WHERE series_id = $1::uuid AND status <> 'cancelled' AND lower(timespan) >= (SELECT lower(timespan) FROM rsvp.reservations WHERE id = $2 AND series_id = $3::uuid) ORDER BY lower(timespan), id FOR UPDATE");
+
+        let selector = SeriesSelector::new(SERIES_ID, 0, SeriesScope::All);
+        assert_eq!(selector.to_sql().into_sql(), "SELECT id FROM rsvp.reservations WHERE series_id = $1::uuid AND status <> 'cancelled' ORDER BY lower(timespan), id FOR UPDATE");
+    }
+}
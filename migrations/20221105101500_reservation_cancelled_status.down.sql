@@ -0,0 +1,7 @@
+-- postgres can't drop a value from an enum, rebuild the type without 'cancelled'
+ALTER TABLE rsvp.reservations ALTER COLUMN status DROP DEFAULT;
+ALTER TYPE rsvp.reservation_status RENAME TO reservation_status_old;
+CREATE TYPE rsvp.reservation_status AS ENUM ('unknow', 'pending', 'confirmed', 'blocked');
+ALTER TABLE rsvp.reservations ALTER COLUMN status TYPE rsvp.reservation_status USING status::text::rsvp.reservation_status;
+ALTER TABLE rsvp.reservations ALTER COLUMN status SET DEFAULT 'pending';
+DROP TYPE rsvp.reservation_status_old;
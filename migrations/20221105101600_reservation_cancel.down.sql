@@ -0,0 +1,10 @@
+-- cancelled reservations may overlap with others, they can't be kept without the partial constraint
+DELETE FROM rsvp.reservations WHERE status = 'cancelled';
+
+ALTER TABLE rsvp.reservations DROP CONSTRAINT reservations_conflict;
+ALTER TABLE rsvp.reservations ADD CONSTRAINT reservations_conflict
+    EXCLUDE USING gist (resource_id WITH =, timespan WITH &&);
+
+ALTER TABLE rsvp.reservations
+    DROP COLUMN cancelled_at,
+    DROP COLUMN cancel_reason;
@@ -0,0 +1,8 @@
+ALTER TABLE rsvp.reservations
+    ADD COLUMN cancelled_at TIMESTAMPTZ,
+    ADD COLUMN cancel_reason TEXT;
+
+-- cancelled reservations stay in the table but no longer hold their timespan
+ALTER TABLE rsvp.reservations DROP CONSTRAINT reservations_conflict;
+ALTER TABLE rsvp.reservations ADD CONSTRAINT reservations_conflict
+    EXCLUDE USING gist (resource_id WITH =, timespan WITH &&) WHERE (status <> 'cancelled');
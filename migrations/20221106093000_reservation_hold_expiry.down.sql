@@ -0,0 +1,3 @@
+DROP INDEX rsvp.reservations_expires_at_idx;
+
+ALTER TABLE rsvp.reservations DROP COLUMN expires_at;
@@ -0,0 +1,4 @@
+-- pending reservations holding a slot are cancelled by the reaper once expired
+ALTER TABLE rsvp.reservations ADD COLUMN expires_at TIMESTAMPTZ;
+
+CREATE INDEX reservations_expires_at_idx ON rsvp.reservations (expires_at) WHERE status = 'pending';
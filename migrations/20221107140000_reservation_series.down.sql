@@ -0,0 +1,3 @@
+DROP INDEX rsvp.reservations_series_id_idx;
+
+ALTER TABLE rsvp.reservations DROP COLUMN series_id;
@@ -0,0 +1,4 @@
+-- occurrences of a recurring reservation share the same series id
+ALTER TABLE rsvp.reservations ADD COLUMN series_id UUID;
+
+CREATE INDEX reservations_series_id_idx ON rsvp.reservations (series_id);
@@ -0,0 +1,3 @@
+DROP INDEX rsvp.reservations_idempotency_key_idx;
+
+ALTER TABLE rsvp.reservations DROP COLUMN idempotency_key;
@@ -0,0 +1,5 @@
+-- key given by the client when making the reservation, retries with the same key return the same reservation
+ALTER TABLE rsvp.reservations ADD COLUMN idempotency_key VARCHAR(64);
+
+CREATE UNIQUE INDEX reservations_idempotency_key_idx ON rsvp.reservations (user_id, idempotency_key)
+    WHERE idempotency_key IS NOT NULL;
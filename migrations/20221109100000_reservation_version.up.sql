@@ -0,0 +1,13 @@
+-- version of the reservation, incremented on every update so that clients could detect concurrent changes
+ALTER TABLE rsvp.reservations ADD COLUMN version BIGINT NOT NULL DEFAULT 1;
+
+CREATE OR REPLACE FUNCTION rsvp.reservations_version_trigger() RETURNS TRIGGER AS $$
+BEGIN
+    NEW.version := OLD.version + 1;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER reservations_version_trigger
+    BEFORE UPDATE ON rsvp.reservations
+    FOR EACH ROW EXECUTE PROCEDURE rsvp.reservations_version_trigger();
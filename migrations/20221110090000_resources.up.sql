@@ -0,0 +1,15 @@
+-- bookable resources, reservations could only be made on active ones
+CREATE TABLE rsvp.resources (
+    id VARCHAR(64) NOT NULL, -- same as resource_id of rsvp.reservations
+    name TEXT NOT NULL DEFAULT '',
+    resource_type TEXT NOT NULL DEFAULT '',
+    metadata JSONB NOT NULL DEFAULT '{}',
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+
+    CONSTRAINT resources_pkey PRIMARY KEY (id)
+);
+
+CREATE INDEX resources_resource_type_idx ON rsvp.resources (resource_type);
+
+-- existing reservations keep their resources bookable
+INSERT INTO rsvp.resources (id, name) SELECT DISTINCT resource_id, resource_id FROM rsvp.reservations;
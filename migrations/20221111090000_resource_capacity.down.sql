@@ -0,0 +1,10 @@
+-- pooled reservations may overlap, they can't be kept without the pooled exception in the constraint
+DELETE FROM rsvp.reservations WHERE pooled AND status <> 'cancelled';
+
+ALTER TABLE rsvp.reservations DROP CONSTRAINT reservations_conflict;
+ALTER TABLE rsvp.reservations ADD CONSTRAINT reservations_conflict
+    EXCLUDE USING gist (resource_id WITH =, timespan WITH &&) WHERE (status <> 'cancelled');
+
+ALTER TABLE rsvp.reservations DROP COLUMN pooled;
+
+ALTER TABLE rsvp.resources DROP COLUMN capacity;
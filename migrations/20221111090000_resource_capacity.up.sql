@@ -0,0 +1,9 @@
+-- how many reservations of the resource could overlap
+ALTER TABLE rsvp.resources ADD COLUMN capacity INTEGER NOT NULL DEFAULT 1 CHECK (capacity >= 1);
+
+-- reservations of resources with capacity above 1 may overlap, their capacity is checked when reserving
+ALTER TABLE rsvp.reservations ADD COLUMN pooled BOOLEAN NOT NULL DEFAULT FALSE;
+
+ALTER TABLE rsvp.reservations DROP CONSTRAINT reservations_conflict;
+ALTER TABLE rsvp.reservations ADD CONSTRAINT reservations_conflict
+    EXCLUDE USING gist (resource_id WITH =, timespan WITH &&) WHERE (status <> 'cancelled' AND NOT pooled);
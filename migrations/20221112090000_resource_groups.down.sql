@@ -0,0 +1,3 @@
+DROP FUNCTION rsvp.resource_members(VARCHAR(64));
+
+ALTER TABLE rsvp.resources DROP COLUMN parent_id;
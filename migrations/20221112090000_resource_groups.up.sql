@@ -0,0 +1,18 @@
+-- resources form a tree, e.g. building -> floor -> room. A resource with children is a group
+ALTER TABLE rsvp.resources ADD COLUMN parent_id VARCHAR(64)
+    REFERENCES rsvp.resources (id) ON DELETE SET NULL
+    CONSTRAINT resources_parent_check CHECK (parent_id <> id);
+
+CREATE INDEX resources_parent_id_idx ON rsvp.resources (parent_id);
+
+-- leaf resources below the group, or the resource itself if it has no children.
+-- Empty if the group is not in the catalog
+CREATE OR REPLACE FUNCTION rsvp.resource_members(group_id VARCHAR(64)) RETURNS SETOF VARCHAR(64) AS $$
+    WITH RECURSIVE tree AS (
+        SELECT id FROM rsvp.resources WHERE id = group_id
+        UNION
+        SELECT r.id FROM rsvp.resources r JOIN tree t ON r.parent_id = t.id
+    )
+    SELECT t.id FROM tree t
+    WHERE NOT EXISTS (SELECT 1 FROM rsvp.resources c WHERE c.parent_id = t.id);
+$$ LANGUAGE sql STABLE;
@@ -0,0 +1,4 @@
+DROP TABLE rsvp.resource_blackouts;
+
+ALTER TABLE rsvp.resources DROP COLUMN opening_hours;
+ALTER TABLE rsvp.resources DROP COLUMN timezone;
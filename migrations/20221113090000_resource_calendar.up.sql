@@ -0,0 +1,16 @@
+-- opening hours are local to the timezone of the resource, an empty timezone is UTC.
+-- A resource without opening hours is always open
+ALTER TABLE rsvp.resources ADD COLUMN timezone TEXT NOT NULL DEFAULT '';
+ALTER TABLE rsvp.resources ADD COLUMN opening_hours JSONB NOT NULL DEFAULT '[]';
+
+-- one-off periods in which the resource could not be reserved, e.g. for maintenance
+CREATE TABLE rsvp.resource_blackouts (
+    id BIGSERIAL NOT NULL,
+    resource_id VARCHAR(64) NOT NULL REFERENCES rsvp.resources (id) ON DELETE CASCADE,
+    timespan TSTZRANGE NOT NULL,
+    reason TEXT NOT NULL DEFAULT '',
+
+    CONSTRAINT resource_blackouts_pkey PRIMARY KEY (id)
+);
+
+CREATE INDEX resource_blackouts_resource_id_timespan_idx ON rsvp.resource_blackouts USING gist (resource_id, timespan);
@@ -0,0 +1,23 @@
+-- a reservation moved to another timespan or with an edited note is a change as well, so that
+-- listeners and the history see it, not only status changes
+CREATE OR REPLACE FUNCTION rsvp.reservations_trigger() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        -- update reservation_changes
+        INSERT INTO rsvp.reservation_changes (reservation_id, old, new, op) VALUES (NEW.id, null, TO_JSONB(NEW), 'create');
+    ELSIF TG_OP = 'UPDATE' THEN
+        -- if status, timespan or note changed, update reservation_changes
+        IF OLD.status <> NEW.status
+            OR OLD.timespan IS DISTINCT FROM NEW.timespan
+            OR OLD.note IS DISTINCT FROM NEW.note THEN
+            INSERT INTO rsvp.reservation_changes (reservation_id, old, new, op) VALUES (NEW.id, TO_JSONB(OLD), TO_JSONB(NEW),  'update');
+        END IF;
+    ELSIF TG_OP = 'DELETE' THEN
+        -- update reservation_changes
+        INSERT INTO rsvp.reservation_changes (reservation_id, old, new, op) VALUES (OLD.id, TO_JSONB(OLD), null, 'delete');
+    END IF;
+    -- notify a channel called reservation_update
+    NOTIFY reservation_update;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
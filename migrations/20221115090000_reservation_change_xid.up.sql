@@ -0,0 +1,6 @@
+-- transaction that made the change. Change ids are taken before commit, so a change could become
+-- visible after one with a higher id. Listeners read changes in (xid, id) order and hold back the
+-- ones of transactions not older than the oldest one still in flight, so none of them is skipped
+ALTER TABLE rsvp.reservation_changes ADD COLUMN xid xid8 NOT NULL DEFAULT pg_current_xact_id();
+
+CREATE INDEX reservation_changes_xid_id_idx ON rsvp.reservation_changes (xid, id);
@@ -0,0 +1,6 @@
+CREATE OR REPLACE FUNCTION rsvp.reservations_version_trigger() RETURNS TRIGGER AS $$
+BEGIN
+    NEW.version := OLD.version + 1;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,12 @@
+-- pooled only follows the capacity of the resource, syncing it is not a change of the reservation
+-- that clients could hold a version of
+CREATE OR REPLACE FUNCTION rsvp.reservations_version_trigger() RETURNS TRIGGER AS $$
+BEGIN
+    IF OLD.pooled IS DISTINCT FROM NEW.pooled
+        AND TO_JSONB(OLD) - 'pooled' - 'version' = TO_JSONB(NEW) - 'pooled' - 'version' THEN
+        RETURN NEW;
+    END IF;
+    NEW.version := OLD.version + 1;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
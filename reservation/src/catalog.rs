@@ -0,0 +1,626 @@
+use std::ops::Bound;
+
+use abi::{ToSql, Validator};
+use async_trait::async_trait;
+use sqlx::{postgres::types::PgRange, types::Json};
+
+use crate::{manager::find_full_interval, ReservationManager, ResourceCatalog};
+
+#[async_trait]
+impl ResourceCatalog for ReservationManager {
+    async fn create_resource(&self, resource: abi::Resource) -> Result<abi::Resource, abi::Error> {
+        resource.validate()?;
+        self.check_parent(&resource).await?;
+        let created = sqlx::query_as(
+            r#"
+                INSERT INTO rsvp.resources
+                    (id, name, resource_type, metadata, active, capacity, parent_id, timezone, opening_hours)
+                VALUES ($1, $2, $3, $4, $5, GREATEST($6, 1), NULLIF($7, ''), $8, $9)
+                ON CONFLICT (id) DO NOTHING
+                RETURNING *
+            "#,
+        )
+        .bind(&resource.id)
+        .bind(&resource.name)
+        .bind(&resource.resource_type)
+        .bind(Json(&resource.metadata))
+        .bind(resource.active)
+        .bind(resource.capacity)
+        .bind(&resource.parent_id)
+        .bind(&resource.timezone)
+        .bind(Json(&resource.opening_hours))
+        .fetch_optional(&self.pool)
+        .await?;
+
+        created.ok_or(abi::Error::ResourceExists(resource.id))
+    }
+
+    async fn get_resource(&self, id: String) -> Result<abi::Resource, abi::Error> {
+        let resource = sqlx::query_as("SELECT * FROM rsvp.resources WHERE id = $1")
+            .bind(&id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        resource.ok_or(abi::Error::UnknownResource(id))
+    }
+
+    /// the capacity can't be lowered below the number of reservations of the resource that
+    /// overlap, the update fails with the first interval in which there are more
+    async fn update_resource(&self, resource: abi::Resource) -> Result<abi::Resource, abi::Error> {
+        resource.validate()?;
+        self.check_parent(&resource).await?;
+        let mut tx = self.pool.begin().await?;
+        let updated: Option<abi::Resource> = sqlx::query_as(
+            r#"
+                UPDATE rsvp.resources SET name = $2, resource_type = $3, metadata = $4, active = $5,
+                    capacity = GREATEST($6, 1), parent_id = NULLIF($7, ''), timezone = $8,
+                    opening_hours = $9
+                WHERE id = $1
+                RETURNING *
+            "#,
+        )
+        .bind(&resource.id)
+        .bind(&resource.name)
+        .bind(&resource.resource_type)
+        .bind(Json(&resource.metadata))
+        .bind(resource.active)
+        .bind(resource.capacity)
+        .bind(&resource.parent_id)
+        .bind(&resource.timezone)
+        .bind(Json(&resource.opening_hours))
+        .fetch_optional(&mut tx)
+        .await?;
+        let Some(updated) = updated else {
+            return Err(abi::Error::UnknownResource(resource.id));
+        };
+
+        // the updated row stays locked until commit, and reservations of a resource with capacity
+        // above 1 lock it as well, so none of them is made meanwhile
+        let all_time = PgRange {
+            start: Bound::Unbounded,
+            end: Bound::Unbounded,
+        };
+        let over =
+            find_full_interval(&mut tx, &updated.id, all_time, updated.capacity + 1, None).await?;
+        if let Some((start, end)) = over {
+            let full = abi::ReservationWindow {
+                rid: updated.id.clone(),
+                start,
+                end,
+            };
+            return Err(abi::Error::ConflictReservation(
+                abi::ReservationConflictInfo::Full(abi::ResourceFull {
+                    new: full.clone(),
+                    full,
+                    capacity: updated.capacity,
+                }),
+            ));
+        }
+
+        // only reservations of pooled resources are left to the capacity check
+        sqlx::query(
+            "UPDATE rsvp.reservations SET pooled = $2 WHERE resource_id = $1 AND pooled <> $2",
+        )
+        .bind(&updated.id)
+        .bind(updated.capacity > 1)
+        .execute(&mut tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(updated)
+    }
+
+    /// reservations on the resource are kept, its children become top level resources
+    async fn delete_resource(&self, id: String) -> Result<abi::Resource, abi::Error> {
+        let resource = sqlx::query_as("DELETE FROM rsvp.resources WHERE id = $1 RETURNING *")
+            .bind(&id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        resource.ok_or(abi::Error::UnknownResource(id))
+    }
+
+    async fn list_resources(
+        &self,
+        request: abi::ListResourcesRequest,
+    ) -> Result<Vec<abi::Resource>, abi::Error> {
+        let resources = request
+            .to_sql()
+            .build_query_as()
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(resources)
+    }
+
+    /// existing reservations overlapping the blackout are kept
+    async fn add_blackout(&self, blackout: abi::Blackout) -> Result<abi::Blackout, abi::Error> {
+        blackout.validate()?;
+        let added = sqlx::query_as(
+            r#"
+                INSERT INTO rsvp.resource_blackouts (resource_id, timespan, reason)
+                SELECT id, $2, $3 FROM rsvp.resources WHERE id = $1
+                RETURNING *
+            "#,
+        )
+        .bind(&blackout.resource_id)
+        .bind(blackout.get_timespan())
+        .bind(&blackout.reason)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        added.ok_or(abi::Error::UnknownResource(blackout.resource_id))
+    }
+
+    async fn remove_blackout(&self, id: i64) -> Result<abi::Blackout, abi::Error> {
+        let blackout =
+            sqlx::query_as("DELETE FROM rsvp.resource_blackouts WHERE id = $1 RETURNING *")
+                .bind(id)
+                .fetch_optional(&self.pool)
+                .await?;
+
+        blackout.ok_or(abi::Error::NotFound)
+    }
+
+    async fn list_blackouts(&self, resource_id: String) -> Result<Vec<abi::Blackout>, abi::Error> {
+        let blackouts = sqlx::query_as(
+            "SELECT * FROM rsvp.resource_blackouts WHERE resource_id = $1 ORDER BY lower(timespan)",
+        )
+        .bind(&resource_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(blackouts)
+    }
+}
+
+impl ReservationManager {
+    /// the parent must be in the catalog and must not be the resource itself or one of its
+    /// descendants, otherwise the groups would form a cycle
+    async fn check_parent(&self, resource: &abi::Resource) -> Result<(), abi::Error> {
+        if resource.parent_id.is_empty() {
+            return Ok(());
+        }
+
+        let in_subtree: Option<bool> = sqlx::query_scalar(
+            r#"
+                WITH RECURSIVE subtree AS (
+                    SELECT $1::varchar AS id
+                    UNION
+                    SELECT r.id FROM rsvp.resources r JOIN subtree s ON r.parent_id = s.id
+                )
+                SELECT id IN (SELECT id FROM subtree) FROM rsvp.resources WHERE id = $2
+            "#,
+        )
+        .bind(&resource.id)
+        .bind(&resource.parent_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match in_subtree {
+            None => Err(abi::Error::UnknownResource(resource.parent_id.clone())),
+            Some(true) => Err(abi::Error::InvalidParent(resource.parent_id.clone())),
+            Some(false) => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use docker_tester::TestPostgres;
+
+    use super::*;
+    use crate::Rsvp;
+
+    #[tokio::test]
+    async fn resource_crud_should_work() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let manager = ReservationManager::new(test_postgres.get_pool().await);
+
+        let room = abi::Resource::new("room-101", "Room 101", "room").with_metadata("seats", "8");
+        let created = manager.create_resource(room.clone()).await.unwrap();
+        assert_eq!(created, room);
+        let err = manager.create_resource(room.clone()).await.unwrap_err();
+        assert_eq!(err, abi::Error::ResourceExists("room-101".into()));
+        manager
+            .create_resource(abi::Resource::new("projector-1", "Projector", "device"))
+            .await
+            .unwrap();
+
+        let updated = manager
+            .update_resource(abi::Resource {
+                name: "Big Room 101".into(),
+                active: false,
+                ..room.clone()
+            })
+            .await
+            .unwrap();
+        assert_eq!(updated.name, "Big Room 101");
+        assert_eq!(
+            manager.get_resource("room-101".into()).await.unwrap(),
+            updated
+        );
+
+        let resources = manager
+            .list_resources(abi::ListResourcesRequest::default())
+            .await
+            .unwrap();
+        assert_eq!(resources.len(), 1);
+        assert_eq!(resources[0].id, "projector-1");
+        let resources = manager
+            .list_resources(abi::ListResourcesRequest {
+                resource_type: "room".into(),
+                include_inactive: true,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(resources, vec![updated.clone()]);
+
+        let deleted = manager.delete_resource("room-101".into()).await.unwrap();
+        assert_eq!(deleted, updated);
+        let err = manager.get_resource("room-101".into()).await.unwrap_err();
+        assert_eq!(err, abi::Error::UnknownResource("room-101".into()));
+    }
+
+    #[tokio::test]
+    async fn reserve_should_reject_unknown_or_inactive_resource() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let manager = ReservationManager::new(test_postgres.get_pool().await);
+        let rsvp = abi::Reservation::new_pending(
+            "user_id_1",
+            "room-101",
+            "2022-12-25T15:00:00-0700".parse().unwrap(),
+            "2022-12-28T12:00:00-0700".parse().unwrap(),
+            "hello.",
+        );
+
+        let err = manager.reserve(rsvp.clone()).await.unwrap_err();
+        assert_eq!(err, abi::Error::UnknownResource("room-101".into()));
+
+        let mut room = abi::Resource::new("room-101", "Room 101", "room");
+        room.active = false;
+        manager.create_resource(room).await.unwrap();
+        let err = manager.reserve(rsvp.clone()).await.unwrap_err();
+        assert_eq!(err, abi::Error::UnknownResource("room-101".into()));
+
+        manager
+            .update_resource(abi::Resource::new("room-101", "Room 101", "room"))
+            .await
+            .unwrap();
+        let rsvp = manager.reserve(rsvp).await.unwrap();
+        assert!(rsvp.id > 0);
+    }
+
+    #[tokio::test]
+    async fn lowering_capacity_should_keep_room_for_overlapping_reservations() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let manager = ReservationManager::new(test_postgres.get_pool().await);
+        let desks = manager
+            .create_resource(abi::Resource::new("desk-pool-1", "Desks", "desk").with_capacity(3))
+            .await
+            .unwrap();
+        for (start, end) in [
+            ("2022-12-20T09:00:00Z", "2022-12-20T12:00:00Z"),
+            ("2022-12-20T10:00:00Z", "2022-12-20T14:00:00Z"),
+            ("2022-12-20T11:00:00Z", "2022-12-20T13:00:00Z"),
+        ] {
+            let rsvp = abi::Reservation::new_pending(
+                "user_id_1",
+                "desk-pool-1",
+                start.parse().unwrap(),
+                end.parse().unwrap(),
+                "desk",
+            );
+            manager.reserve(rsvp).await.unwrap();
+        }
+
+        // three desks are taken from 11:00 to 12:00
+        let err = manager
+            .update_resource(desks.clone().with_capacity(2))
+            .await
+            .unwrap_err();
+        let full = abi::ReservationWindow {
+            rid: "desk-pool-1".into(),
+            start: "2022-12-20T11:00:00Z".parse().unwrap(),
+            end: "2022-12-20T12:00:00Z".parse().unwrap(),
+        };
+        assert_eq!(
+            err,
+            abi::Error::ConflictReservation(abi::ReservationConflictInfo::Full(
+                abi::ResourceFull {
+                    new: full.clone(),
+                    full,
+                    capacity: 2,
+                }
+            ))
+        );
+        assert_eq!(
+            manager.get_resource("desk-pool-1".into()).await.unwrap(),
+            desks
+        );
+
+        // pooling the reservations of a resource does not change their version
+        let room = manager
+            .create_resource(abi::Resource::new("room-1", "Room 1", "room"))
+            .await
+            .unwrap();
+        let rsvp = abi::Reservation::new_pending(
+            "user_id_1",
+            "room-1",
+            "2022-12-20T09:00:00Z".parse().unwrap(),
+            "2022-12-20T12:00:00Z".parse().unwrap(),
+            "meeting",
+        );
+        let rsvp = manager.reserve(rsvp).await.unwrap();
+        manager
+            .update_resource(room.with_capacity(2))
+            .await
+            .unwrap();
+        assert_eq!(manager.get(rsvp.id).await.unwrap(), rsvp);
+    }
+
+    #[tokio::test]
+    async fn resource_groups_should_expand_to_members() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let manager = ReservationManager::new(test_postgres.get_pool().await);
+        for resource in [
+            abi::Resource::new("building-1", "Building 1", "building"),
+            abi::Resource::new("floor-3", "Floor 3", "floor").with_parent("building-1"),
+            abi::Resource::new("room-301", "Room 301", "room").with_parent("floor-3"),
+            abi::Resource::new("room-302", "Room 302", "room").with_parent("floor-3"),
+        ] {
+            manager.create_resource(resource).await.unwrap();
+        }
+
+        let err = manager
+            .create_resource(
+                abi::Resource::new("room-303", "Room 303", "room").with_parent("floor-9"),
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err, abi::Error::UnknownResource("floor-9".into()));
+        let err = manager
+            .update_resource(
+                abi::Resource::new("building-1", "Building 1", "building").with_parent("room-301"),
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err, abi::Error::InvalidParent("room-301".into()));
+
+        let rooms = manager
+            .list_resources(abi::ListResourcesRequest {
+                parent_id: "floor-3".into(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let ids: Vec<_> = rooms.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, ["room-301", "room-302"]);
+
+        // any free room on floor 3, in id order
+        let rsvp = abi::Reservation::new_pending(
+            "user_id_1",
+            "",
+            "2022-12-25T15:00:00-0700".parse().unwrap(),
+            "2022-12-28T12:00:00-0700".parse().unwrap(),
+            "hello.",
+        );
+        let mut chosen = Vec::new();
+        for _ in 0..2 {
+            let rsvp = manager
+                .reserve_in_group(rsvp.clone(), "floor-3".into(), "".into())
+                .await
+                .unwrap();
+            chosen.push(rsvp.resource_id);
+        }
+        assert_eq!(chosen, ["room-301", "room-302"]);
+        let err = manager
+            .reserve_in_group(rsvp.clone(), "building-1".into(), "".into())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, abi::Error::ConflictReservations(infos) if infos.len() == 2));
+
+        let filter = abi::ReservationFilterBuilder::default()
+            .group_id("building-1")
+            .build()
+            .unwrap();
+        let (_, rsvps) = manager.filter(filter).await.unwrap();
+        assert_eq!(rsvps.len(), 2);
+
+        let request = abi::AvailabilityRequestBuilder::default()
+            .group_id("floor-3")
+            .start(
+                "2022-12-28T00:00:00Z"
+                    .parse::<prost_types::Timestamp>()
+                    .unwrap(),
+            )
+            .end(
+                "2022-12-29T00:00:00Z"
+                    .parse::<prost_types::Timestamp>()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+        let slots = manager.availability(request).await.unwrap();
+        let ids: Vec<_> = slots.iter().map(|s| s.resource_id.as_str()).collect();
+        assert_eq!(ids, ["room-301", "room-302"]);
+    }
+
+    #[tokio::test]
+    async fn opening_hours_and_blackouts_should_block_reservations() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let manager = ReservationManager::new(test_postgres.get_pool().await);
+        let hours = (1..=5).map(|weekday| abi::OpeningHours {
+            weekday,
+            opens_secs: 9 * 3600,
+            closes_secs: 17 * 3600,
+        });
+        let lab = abi::Resource::new("lab-1", "Lab 1", "lab").with_opening_hours("UTC", hours);
+        let lab = manager.create_resource(lab.clone()).await.unwrap();
+        assert_eq!(manager.get_resource("lab-1".into()).await.unwrap(), lab);
+        let utc = |s: &str| s.parse().unwrap();
+        // 2022-12-05 is a Monday
+        let rsvp = |start: &str, end: &str| {
+            abi::Reservation::new_pending(
+                "user_id_1",
+                "lab-1",
+                start.parse().unwrap(),
+                end.parse().unwrap(),
+                "experiment",
+            )
+        };
+
+        let err = manager
+            .reserve(rsvp("2022-12-05T08:00:00Z", "2022-12-05T10:00:00Z"))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err,
+            abi::Error::ResourceClosed("lab-1".into(), utc("2022-12-05T08:00:00Z"))
+        );
+        let morning = manager
+            .reserve(rsvp("2022-12-05T09:00:00Z", "2022-12-05T11:00:00Z"))
+            .await
+            .unwrap();
+
+        let err = manager
+            .add_blackout(abi::Blackout::new(
+                "lab-2",
+                "2022-12-05T13:00:00Z".parse().unwrap(),
+                "2022-12-05T14:00:00Z".parse().unwrap(),
+                "maintenance",
+            ))
+            .await
+            .unwrap_err();
+        assert_eq!(err, abi::Error::UnknownResource("lab-2".into()));
+        let blackout = manager
+            .add_blackout(abi::Blackout::new(
+                "lab-1",
+                "2022-12-05T13:00:00Z".parse().unwrap(),
+                "2022-12-05T14:00:00Z".parse().unwrap(),
+                "maintenance",
+            ))
+            .await
+            .unwrap();
+        assert!(blackout.id > 0);
+        assert_eq!(
+            manager.list_blackouts("lab-1".into()).await.unwrap(),
+            vec![blackout.clone()]
+        );
+
+        let blacked_out = abi::Error::ResourceBlackedOut(
+            "lab-1".into(),
+            utc("2022-12-05T13:00:00Z"),
+            utc("2022-12-05T14:00:00Z"),
+            "maintenance".into(),
+        );
+        let afternoon = rsvp("2022-12-05T12:00:00Z", "2022-12-05T15:00:00Z");
+        let err = manager.check(afternoon.clone()).await.unwrap_err();
+        assert_eq!(err, blacked_out);
+        let err = manager.reserve(afternoon.clone()).await.unwrap_err();
+        assert_eq!(err, blacked_out);
+        let err = manager
+            .reschedule(
+                morning.id,
+                utc("2022-12-05T13:30:00Z"),
+                utc("2022-12-05T14:30:00Z"),
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err, blacked_out);
+
+        // the lab is free before the blackout and after it until closing time
+        let request = abi::AvailabilityRequestBuilder::default()
+            .resource_ids(vec!["lab-1".to_string()])
+            .start(
+                "2022-12-05T00:00:00Z"
+                    .parse::<prost_types::Timestamp>()
+                    .unwrap(),
+            )
+            .end(
+                "2022-12-06T00:00:00Z"
+                    .parse::<prost_types::Timestamp>()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+        let slots = manager.availability(request).await.unwrap();
+        assert_eq!(
+            slots,
+            vec![
+                abi::FreeSlot::new(
+                    "lab-1",
+                    utc("2022-12-05T11:00:00Z"),
+                    utc("2022-12-05T13:00:00Z")
+                ),
+                abi::FreeSlot::new(
+                    "lab-1",
+                    utc("2022-12-05T14:00:00Z"),
+                    utc("2022-12-05T17:00:00Z")
+                ),
+            ]
+        );
+
+        let removed = manager.remove_blackout(blackout.id).await.unwrap();
+        assert_eq!(removed, blackout);
+        let err = manager.remove_blackout(blackout.id).await.unwrap_err();
+        assert_eq!(err, abi::Error::NotFound);
+        manager.reserve(afternoon).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn reserve_any_should_skip_closed_resources() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let manager = ReservationManager::new(test_postgres.get_pool().await);
+        let hours = (1..=5).map(|weekday| abi::OpeningHours {
+            weekday,
+            opens_secs: 9 * 3600,
+            closes_secs: 17 * 3600,
+        });
+        for resource in [
+            abi::Resource::new("labs", "Labs", "group"),
+            abi::Resource::new("lab-1", "Lab 1", "lab")
+                .with_parent("labs")
+                .with_opening_hours("UTC", hours),
+            abi::Resource::new("lab-2", "Lab 2", "lab").with_parent("labs"),
+        ] {
+            manager.create_resource(resource).await.unwrap();
+        }
+        // 2022-12-05 is a Monday, lab-1 is still closed at 8:00
+        let rsvp = abi::Reservation::new_pending(
+            "user_id_1",
+            "",
+            "2022-12-05T08:00:00Z".parse().unwrap(),
+            "2022-12-05T10:00:00Z".parse().unwrap(),
+            "experiment",
+        );
+
+        let reserved = manager
+            .reserve_any(
+                rsvp.clone(),
+                vec!["lab-1".into(), "lab-2".into()],
+                "".into(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(reserved.resource_id, "lab-2");
+
+        let err = manager
+            .reserve_in_group(rsvp, "labs".into(), "".into())
+            .await
+            .unwrap_err();
+        let abi::Error::ConflictReservations(infos) = err else {
+            panic!("expected conflicts, got {:?}", err);
+        };
+        assert_eq!(infos.len(), 2);
+        let abi::ReservationConflictInfo::Unavailable(unavailable) = &infos[0] else {
+            panic!("expected lab-1 to be unavailable, got {:?}", infos[0]);
+        };
+        assert_eq!(unavailable.new.rid, "lab-1");
+        assert_eq!(
+            unavailable.reason,
+            abi::Error::ResourceClosed("lab-1".into(), "2022-12-05T08:00:00Z".parse().unwrap())
+                .to_string()
+        );
+        assert!(matches!(infos[1], abi::ReservationConflictInfo::Parsed(_)));
+    }
+}
@@ -1,18 +1,35 @@
+mod catalog;
 mod manager;
 
+pub use manager::HOLD_EXPIRED_REASON;
+
 use abi::{DbConfig, ReservationId};
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use sqlx::{postgres::PgPoolOptions, PgPool};
 use tokio::sync::mpsc;
 
-#[derive(Debug)]
+/// result of one id in a batch operation
+pub type BatchResult = Result<abi::Reservation, abi::Error>;
+
+#[derive(Debug, Clone)]
 pub struct ReservationManager {
     pool: PgPool,
+    /// default ttl of pending reservations in seconds, 0 means they never expire
+    hold_ttl_secs: i64,
 }
 
 impl ReservationManager {
     pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            hold_ttl_secs: 0,
+        }
+    }
+
+    pub fn with_hold_ttl(mut self, ttl_secs: u64) -> Self {
+        self.hold_ttl_secs = ttl_secs as i64;
+        self
     }
 
     pub async fn from_config(config: &DbConfig) -> Result<Self, abi::Error> {
@@ -29,18 +46,118 @@ impl ReservationManager {
 pub trait Rsvp {
     /// make a reservation
     async fn reserve(&self, mut rsvp: abi::Reservation) -> Result<abi::Reservation, abi::Error>;
-    /// change reservation status (if current status is pending, change it to confirmed)
-    async fn change_status(&self, id: ReservationId) -> Result<abi::Reservation, abi::Error>;
-    /// update note
+    /// make a reservation, retries with the same idempotency key by the same user return the
+    /// reservation made by the first attempt. An empty key makes it the same as reserve
+    async fn reserve_idempotent(
+        &self,
+        rsvp: abi::Reservation,
+        idempotency_key: String,
+    ) -> Result<abi::Reservation, abi::Error>;
+    /// reserve the first active member of the group free for the window, members are tried in
+    /// id order. Fails with the conflict of every member if none is free
+    async fn reserve_in_group(
+        &self,
+        rsvp: abi::Reservation,
+        group_id: String,
+        idempotency_key: String,
+    ) -> Result<abi::Reservation, abi::Error>;
+    /// reserve the first of the resources free for the window, in the order given. Fails with
+    /// the conflict of every resource if none is free
+    async fn reserve_any(
+        &self,
+        rsvp: abi::Reservation,
+        resource_ids: Vec<String>,
+        idempotency_key: String,
+    ) -> Result<abi::Reservation, abi::Error>;
+    /// make all the reservations in one transaction, nothing is reserved if any of them conflicts
+    async fn reserve_many(
+        &self,
+        rsvps: Vec<abi::Reservation>,
+    ) -> Result<Vec<abi::Reservation>, abi::Error>;
+    /// make a reservation for every occurrence of the recurrence, return the series id
+    async fn reserve_recurring(
+        &self,
+        request: abi::ReserveRecurringRequest,
+    ) -> Result<(String, Vec<abi::Occurrence>), abi::Error>;
+    /// validate a reservation like reserve does, return the existing reservations conflicting with it
+    async fn check(&self, rsvp: abi::Reservation) -> Result<Vec<abi::Reservation>, abi::Error>;
+    /// change reservation status (if current status is pending, change it to confirmed).
+    /// If expected_version is given, fail with VersionMismatch if the reservation is at another version
+    async fn change_status(
+        &self,
+        id: ReservationId,
+        expected_version: Option<i64>,
+    ) -> Result<abi::Reservation, abi::Error>;
+    /// move reservation to another status, only transitions in ReservationStatus::TRANSITIONS are allowed.
+    /// If expected_version is given, fail with VersionMismatch if the reservation is at another version
+    async fn transition(
+        &self,
+        id: ReservationId,
+        status: abi::ReservationStatus,
+        expected_version: Option<i64>,
+    ) -> Result<abi::Reservation, abi::Error>;
+    /// update note, if expected_version is given fail with VersionMismatch if the reservation
+    /// is at another version
     async fn update_note(
         &self,
         id: ReservationId,
         note: String,
+        expected_version: Option<i64>,
     ) -> Result<abi::Reservation, abi::Error>;
+    /// change the timespan of the reservation in place
+    async fn reschedule(
+        &self,
+        id: ReservationId,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<abi::Reservation, abi::Error>;
+    /// cancel reservation, it is kept with cancelled status and frees its timespan.
+    /// If expected_version is given, fail with VersionMismatch if the reservation is at another version
+    async fn cancel(
+        &self,
+        id: ReservationId,
+        reason: String,
+        expected_version: Option<i64>,
+    ) -> Result<abi::Reservation, abi::Error>;
+    /// update note of the selected occurrences of a recurring series
+    async fn update_series_note(
+        &self,
+        selector: abi::SeriesSelector,
+        note: String,
+    ) -> Result<Vec<abi::Reservation>, abi::Error>;
+    /// cancel the selected occurrences of a recurring series
+    async fn cancel_series(
+        &self,
+        selector: abi::SeriesSelector,
+        reason: String,
+    ) -> Result<Vec<abi::Reservation>, abi::Error>;
+    /// move the selected occurrences of a recurring series by offset, nothing is moved on conflict
+    async fn shift_series(
+        &self,
+        selector: abi::SeriesSelector,
+        offset: chrono::Duration,
+    ) -> Result<Vec<abi::Reservation>, abi::Error>;
+    /// confirm pending reservations, results are in the order of the ids. In transactional mode
+    /// nothing is confirmed if any of them fails, and the others fail with Aborted
+    async fn confirm_many(
+        &self,
+        ids: Vec<ReservationId>,
+        transactional: bool,
+    ) -> Result<Vec<BatchResult>, abi::Error>;
+    /// cancel reservations, results are in the order of the ids. In transactional mode
+    /// nothing is cancelled if any of them fails, and the others fail with Aborted
+    async fn cancel_many(
+        &self,
+        ids: Vec<ReservationId>,
+        reason: String,
+        transactional: bool,
+    ) -> Result<Vec<BatchResult>, abi::Error>;
     /// delete reservation
     async fn delete(&self, id: ReservationId) -> Result<abi::Reservation, abi::Error>;
     /// get reservation by id
     async fn get(&self, id: ReservationId) -> Result<abi::Reservation, abi::Error>;
+    /// get reservations by ids, results are in the order of the ids
+    async fn get_many(&self, ids: Vec<ReservationId>) -> Result<Vec<BatchResult>, abi::Error>;
     /// query reservation
     async fn query(
         &self,
@@ -51,4 +168,44 @@ pub trait Rsvp {
         &self,
         filter: abi::ReservationFilter,
     ) -> Result<(abi::FilterPager, Vec<abi::Reservation>), abi::Error>;
+    /// listen to reservation changes, resuming from the consumer cursor or last change id if given
+    async fn listen(
+        &self,
+        request: abi::ListenRequest,
+    ) -> mpsc::Receiver<Result<abi::ListenResponse, abi::Error>>;
+    /// record that the consumer has handled every change up to the acknowledged one
+    async fn ack(&self, request: abi::AckRequest) -> Result<(), abi::Error>;
+    /// change history of a reservation order by change id
+    async fn history(
+        &self,
+        request: abi::HistoryRequest,
+    ) -> Result<(abi::FilterPager, Vec<abi::ReservationChange>), abi::Error>;
+    /// free slots of the resources within the search window, order by resource id and start time
+    async fn availability(
+        &self,
+        request: abi::AvailabilityRequest,
+    ) -> Result<Vec<abi::FreeSlot>, abi::Error>;
+}
+
+#[async_trait]
+pub trait ResourceCatalog {
+    /// add a resource to the catalog
+    async fn create_resource(&self, resource: abi::Resource) -> Result<abi::Resource, abi::Error>;
+    /// get resource by id
+    async fn get_resource(&self, id: String) -> Result<abi::Resource, abi::Error>;
+    /// replace every field of the resource except id
+    async fn update_resource(&self, resource: abi::Resource) -> Result<abi::Resource, abi::Error>;
+    /// delete resource from the catalog
+    async fn delete_resource(&self, id: String) -> Result<abi::Resource, abi::Error>;
+    /// list resources order by id
+    async fn list_resources(
+        &self,
+        request: abi::ListResourcesRequest,
+    ) -> Result<Vec<abi::Resource>, abi::Error>;
+    /// block the resource for a period, reservations can't be made or moved into it
+    async fn add_blackout(&self, blackout: abi::Blackout) -> Result<abi::Blackout, abi::Error>;
+    /// remove blackout by id
+    async fn remove_blackout(&self, id: i64) -> Result<abi::Blackout, abi::Error>;
+    /// list blackouts of the resource order by start
+    async fn list_blackouts(&self, resource_id: String) -> Result<Vec<abi::Blackout>, abi::Error>;
 }
@@ -1,13 +1,38 @@
-use abi::{Normalizer, ToSql, Validator};
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Bound,
+    time::Duration,
+};
+
+use abi::{Normalizer, ReservationConflictInfo, ToSql, Validator};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use sqlx::Row;
-use sqlx::{postgres::types::PgRange, Either};
+use sqlx::{
+    postgres::{types::PgRange, PgListener, PgRow},
+    types::Uuid,
+    Connection, Either, FromRow, PgConnection, PgExecutor, PgPool, Postgres, Row, Transaction,
+};
 use tokio::sync::mpsc;
 use tokio_stream::StreamExt;
 use tracing::{info, warn};
 
-use crate::{ReservationId, ReservationManager, Rsvp};
+use crate::{BatchResult, ReservationId, ReservationManager, Rsvp};
+
+/// channel notified by rsvp.reservations_trigger() on every change
+const CHANGE_CHANNEL: &str = "reservation_update";
+/// how often changes held back by a transaction in flight are read again
+const HELD_BACK_RETRY: Duration = Duration::from_millis(100);
+
+/// cancel_reason of the reservations cancelled by the reaper
+pub const HOLD_EXPIRED_REASON: &str = "hold expired";
+
+/// max length of the idempotency key, same as the idempotency_key column
+const MAX_IDEMPOTENCY_KEY_LEN: usize = 64;
+
+/// max number of pending reservations expired in one statement
+const HOLD_REAP_BATCH: i64 = 100;
+
+type ChangeSender = mpsc::Sender<Result<abi::ListenResponse, abi::Error>>;
 
 #[async_trait]
 impl Rsvp for ReservationManager {
@@ -15,45 +40,265 @@ impl Rsvp for ReservationManager {
     async fn reserve(&self, mut rsvp: abi::Reservation) -> Result<abi::Reservation, abi::Error> {
         rsvp.validate()?;
 
-        let status = abi::ReservationStatus::from_i32(rsvp.status)
-            .unwrap_or(abi::ReservationStatus::Pending);
+        match self.insert_one(&mut rsvp, None).await {
+            Ok(()) => Ok(rsvp),
+            Err(e) => Err(with_conflicting_id(&self.pool, e).await),
+        }
+    }
+
+    /// the key is looked up again if the insert fails, so that a retry racing with its first
+    /// attempt returns the reservation of the first attempt instead of a conflict
+    async fn reserve_idempotent(
+        &self,
+        mut rsvp: abi::Reservation,
+        idempotency_key: String,
+    ) -> Result<abi::Reservation, abi::Error> {
+        if idempotency_key.is_empty() {
+            return self.reserve(rsvp).await;
+        }
+        rsvp.validate()?;
+        if idempotency_key.len() > MAX_IDEMPOTENCY_KEY_LEN {
+            return Err(abi::Error::InvalidIdempotencyKey(idempotency_key));
+        }
+
+        if let Some(rsvp) = self.get_by_key(&rsvp.user_id, &idempotency_key).await? {
+            return Ok(rsvp);
+        }
+
+        match self.insert_one(&mut rsvp, Some(&idempotency_key)).await {
+            Ok(()) => Ok(rsvp),
+            Err(e) => match self.get_by_key(&rsvp.user_id, &idempotency_key).await? {
+                Some(rsvp) => Ok(rsvp),
+                None => Err(with_conflicting_id(&self.pool, e).await),
+            },
+        }
+    }
+
+    /// duplicated candidates are only tried once
+    async fn reserve_any(
+        &self,
+        rsvp: abi::Reservation,
+        mut resource_ids: Vec<String>,
+        idempotency_key: String,
+    ) -> Result<abi::Reservation, abi::Error> {
+        if let Some(id) = resource_ids.iter().find(|id| id.is_empty()) {
+            return Err(abi::Error::InvalidResourceId(id.clone()));
+        }
+        if resource_ids.is_empty() {
+            return Err(abi::Error::InvalidResourceId("".into()));
+        }
+        let mut seen = HashSet::new();
+        resource_ids.retain(|id| seen.insert(id.clone()));
 
-        let timespan: PgRange<DateTime<Utc>> = rsvp.get_timespan();
+        self.reserve_first_free(rsvp, &resource_ids, &idempotency_key)
+            .await
+    }
 
-        // generate a insert sql for the reservation
-        // execute the sql
-        let id: i64 = sqlx::query(
+    async fn reserve_in_group(
+        &self,
+        rsvp: abi::Reservation,
+        group_id: String,
+        idempotency_key: String,
+    ) -> Result<abi::Reservation, abi::Error> {
+        let members: Vec<String> = sqlx::query_scalar(
             r#"
-                INSERT INTO rsvp.reservations (user_id, resource_id, timespan, note, status)
-                VALUES ($1, $2, $3, $4, $5::rsvp.reservation_status) RETURNING id
+                SELECT id FROM rsvp.resources
+                WHERE id IN (SELECT rsvp.resource_members($1)) AND active
+                ORDER BY id
             "#,
         )
-        .bind(rsvp.user_id.clone())
-        .bind(rsvp.resource_id.clone())
-        .bind(timespan)
-        .bind(rsvp.note.clone())
-        .bind(status.to_string())
-        .fetch_one(&self.pool)
-        .await?
-        .get(0);
+        .bind(&group_id)
+        .fetch_all(&self.pool)
+        .await?;
+        if members.is_empty() {
+            return Err(abi::Error::UnknownResource(group_id));
+        }
 
-        rsvp.id = id;
-        Ok(rsvp)
+        self.reserve_first_free(rsvp, &members, &idempotency_key)
+            .await
     }
 
-    /// change reservation status (if current status is pending, change it to confirmed, otherwise do nothing)
-    async fn change_status(&self, id: ReservationId) -> Result<abi::Reservation, abi::Error> {
-        id.validate()?;
-        let rsvp: abi::Reservation = sqlx::query_as(
+    /// every reservation is inserted in its own savepoint, so that all conflicts are collected
+    /// before the transaction is rolled back. Conflicting ids are looked up after the rollback,
+    /// so a conflict between two reservations of the request has no conflicting id
+    async fn reserve_many(
+        &self,
+        mut rsvps: Vec<abi::Reservation>,
+    ) -> Result<Vec<abi::Reservation>, abi::Error> {
+        for rsvp in rsvps.iter() {
+            rsvp.validate()?;
+        }
+
+        let mut tx = self.pool.begin().await?;
+        let rids: Vec<_> = rsvps.iter().map(|r| r.resource_id.clone()).collect();
+        let resources = lock_resources(&mut tx, &rids).await?;
+        let mut conflicts = Vec::new();
+        for rsvp in rsvps.iter_mut() {
+            let hold_ttl_secs = self.hold_ttl_secs_of(rsvp);
+            let resource = resources.get(&rsvp.resource_id);
+            let mut savepoint = tx.begin().await?;
+            match insert_reservation(&mut savepoint, rsvp, resource, hold_ttl_secs, None, None)
+                .await
+            {
+                Ok(()) => savepoint.commit().await?,
+                Err(e) => {
+                    savepoint.rollback().await?;
+                    match e {
+                        e @ abi::Error::ConflictReservation(_) => conflicts.push(e),
+                        e => return Err(e),
+                    }
+                }
+            }
+        }
+
+        if conflicts.is_empty() {
+            tx.commit().await?;
+            return Ok(rsvps);
+        }
+
+        tx.rollback().await?;
+        Err(with_conflicting_ids(&self.pool, conflicts).await)
+    }
+
+    /// every occurrence is inserted in its own savepoint, so that a conflict only rolls back
+    /// that occurrence. In all-or-nothing mode the whole transaction is rolled back on conflict
+    async fn reserve_recurring(
+        &self,
+        request: abi::ReserveRecurringRequest,
+    ) -> Result<(String, Vec<abi::Occurrence>), abi::Error> {
+        let rsvps = request.occurrences()?;
+
+        let mut tx = self.pool.begin().await?;
+        let series_id: Uuid = sqlx::query_scalar("SELECT gen_random_uuid()")
+            .fetch_one(&mut tx)
+            .await?;
+
+        let rids: Vec<_> = rsvps.iter().map(|r| r.resource_id.clone()).collect();
+        let resources = lock_resources(&mut tx, &rids).await?;
+        let mut occurrences = Vec::with_capacity(rsvps.len());
+        let mut conflicted = false;
+        for mut rsvp in rsvps {
+            let hold_ttl_secs = self.hold_ttl_secs_of(&rsvp);
+            let resource = resources.get(&rsvp.resource_id);
+            let mut savepoint = tx.begin().await?;
+            let conflict = match insert_reservation(
+                &mut savepoint,
+                &mut rsvp,
+                resource,
+                hold_ttl_secs,
+                Some(series_id),
+                None,
+            )
+            .await
+            {
+                Ok(()) => {
+                    savepoint.commit().await?;
+                    rsvp.series_id = series_id.to_string();
+                    None
+                }
+                Err(e) => {
+                    savepoint.rollback().await?;
+                    // a closed or blacked-out occurrence is reported like a taken one
+                    let e = match e {
+                        e @ (abi::Error::ResourceClosed(..)
+                        | abi::Error::ResourceBlackedOut(..)) => unavailable(&rsvp, e),
+                        e => with_conflicting_id(&mut tx, e).await,
+                    };
+                    match e {
+                        abi::Error::ConflictReservation(info) => Some((&info).into()),
+                        e => return Err(e),
+                    }
+                }
+            };
+            conflicted |= conflict.is_some();
+            occurrences.push(abi::Occurrence {
+                reservation: Some(rsvp),
+                conflict,
+            });
+        }
+
+        if conflicted && request.mode() == abi::RecurrenceMode::AllOrNothing {
+            tx.rollback().await?;
+            // nothing is reserved, report the occurrences without the ids
+            for occurrence in occurrences.iter_mut() {
+                let rsvp = occurrence.reservation.as_mut().unwrap();
+                rsvp.id = 0;
+                rsvp.version = 0;
+                rsvp.expires_at = None;
+                rsvp.series_id = String::new();
+            }
+            return Ok((String::new(), occurrences));
+        }
+
+        tx.commit().await?;
+        let series_id = if occurrences.iter().any(|o| o.conflict.is_none()) {
+            series_id.to_string()
+        } else {
+            String::new()
+        };
+        Ok((series_id, occurrences))
+    }
+
+    /// dry-run of reserve, conflicts are the reservations overlapping on the same resource.
+    /// Reservations of a resource with capacity above 1 only conflict if it is full
+    async fn check(&self, rsvp: abi::Reservation) -> Result<Vec<abi::Reservation>, abi::Error> {
+        rsvp.validate()?;
+        let resource: Option<abi::Resource> =
+            sqlx::query_as("SELECT * FROM rsvp.resources WHERE id = $1 AND active")
+                .bind(&rsvp.resource_id)
+                .fetch_optional(&self.pool)
+                .await?;
+        let Some(resource) = resource else {
+            return Err(abi::Error::UnknownResource(rsvp.resource_id));
+        };
+
+        let window = window_of(&rsvp);
+        let capacity = resource.capacity;
+        let mut conn = self.pool.acquire().await?;
+        check_calendar(&mut conn, &resource, &window).await?;
+        match check_capacity(&mut conn, &window, capacity, None).await {
+            Ok(()) if capacity > 1 => return Ok(vec![]),
+            Ok(()) | Err(abi::Error::ConflictReservation(_)) => {}
+            Err(e) => return Err(e),
+        }
+
+        let conflicts = sqlx::query_as(
             r#"
-                UPDATE rsvp.reservations SET status = 'confirmed' WHERE id = $1 AND status = 'pending'
-                RETURNING *
+                SELECT * FROM rsvp.reservations
+                WHERE resource_id = $1 AND timespan && $2 AND status <> 'cancelled'
+                ORDER BY lower(timespan)
             "#,
         )
-        .bind(id)
-        .fetch_one(&self.pool).await?;
+        .bind(rsvp.resource_id.clone())
+        .bind(rsvp.get_timespan())
+        .fetch_all(&self.pool)
+        .await?;
 
-        Ok(rsvp)
+        Ok(conflicts)
+    }
+
+    /// change reservation status (if current status is pending, change it to confirmed, otherwise fail)
+    async fn change_status(
+        &self,
+        id: ReservationId,
+        expected_version: Option<i64>,
+    ) -> Result<abi::Reservation, abi::Error> {
+        self.update_status(
+            id,
+            abi::ReservationStatus::Confirmed,
+            None,
+            expected_version,
+        )
+        .await
+    }
+
+    async fn transition(
+        &self,
+        id: ReservationId,
+        status: abi::ReservationStatus,
+        expected_version: Option<i64>,
+    ) -> Result<abi::Reservation, abi::Error> {
+        self.update_status(id, status, None, expected_version).await
     }
 
     /// update note
@@ -61,20 +306,104 @@ impl Rsvp for ReservationManager {
         &self,
         id: ReservationId,
         note: String,
+        expected_version: Option<i64>,
     ) -> Result<abi::Reservation, abi::Error> {
         id.validate()?;
-        let rsvp: abi::Reservation = sqlx::query_as(
+        let rsvp: Option<abi::Reservation> = sqlx::query_as(
             r#"
-                UPDATE rsvp.reservations SET note = $1 WHERE id = $2
+                UPDATE rsvp.reservations SET note = $1
+                WHERE id = $2 AND ($3::bigint IS NULL OR version = $3)
                 RETURNING *
             "#,
         )
         .bind(note)
         .bind(id)
-        .fetch_one(&self.pool)
+        .bind(expected_version)
+        .fetch_optional(&self.pool)
         .await?;
 
-        Ok(rsvp)
+        match (rsvp, expected_version) {
+            (Some(rsvp), _) => Ok(rsvp),
+            // the reservation exists but it was changed by someone else
+            (None, Some(expected)) => {
+                let current = self.get(id).await?;
+                Err(abi::Error::VersionMismatch(expected, current.version))
+            }
+            (None, None) => Err(abi::Error::NotFound),
+        }
+    }
+
+    /// change timespan, overlaps are rejected by the reservations_conflict constraint or the
+    /// capacity check of the resource
+    async fn reschedule(
+        &self,
+        id: ReservationId,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<abi::Reservation, abi::Error> {
+        id.validate()?;
+        if start >= end {
+            return Err(abi::Error::InvalidTime);
+        }
+
+        let mut tx = self.pool.begin().await?;
+        match move_reservation(&mut tx, id, start, end).await {
+            Ok(rsvp) => {
+                tx.commit().await?;
+                Ok(rsvp)
+            }
+            Err(e) => {
+                tx.rollback().await?;
+                Err(with_conflicting_id(&self.pool, e).await)
+            }
+        }
+    }
+
+    /// cancel reservation, the row is kept so it could still be queried
+    async fn cancel(
+        &self,
+        id: ReservationId,
+        reason: String,
+        expected_version: Option<i64>,
+    ) -> Result<abi::Reservation, abi::Error> {
+        self.update_status(
+            id,
+            abi::ReservationStatus::Cancelled,
+            Some(reason),
+            expected_version,
+        )
+        .await
+    }
+
+    async fn confirm_many(
+        &self,
+        ids: Vec<ReservationId>,
+        transactional: bool,
+    ) -> Result<Vec<BatchResult>, abi::Error> {
+        self.update_statuses(
+            &ids,
+            abi::ReservationStatus::Confirmed,
+            None,
+            None,
+            transactional,
+        )
+        .await
+    }
+
+    async fn cancel_many(
+        &self,
+        ids: Vec<ReservationId>,
+        reason: String,
+        transactional: bool,
+    ) -> Result<Vec<BatchResult>, abi::Error> {
+        self.update_statuses(
+            &ids,
+            abi::ReservationStatus::Cancelled,
+            Some(reason),
+            None,
+            transactional,
+        )
+        .await
     }
 
     /// delete reservation
@@ -92,6 +421,106 @@ impl Rsvp for ReservationManager {
 
         Ok(rsvp)
     }
+
+    async fn update_series_note(
+        &self,
+        selector: abi::SeriesSelector,
+        note: String,
+    ) -> Result<Vec<abi::Reservation>, abi::Error> {
+        let mut tx = self.pool.begin().await?;
+        let ids = select_series(&mut tx, &selector).await?;
+        let rsvps = sqlx::query_as(
+            r#"
+                WITH updated AS (
+                    UPDATE rsvp.reservations SET note = $1 WHERE id = ANY($2)
+                    RETURNING *
+                )
+                SELECT * FROM updated ORDER BY lower(timespan), id
+            "#,
+        )
+        .bind(note)
+        .bind(ids)
+        .fetch_all(&mut tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(rsvps)
+    }
+
+    /// every selected occurrence is not cancelled, so all of them could move to cancelled
+    async fn cancel_series(
+        &self,
+        selector: abi::SeriesSelector,
+        reason: String,
+    ) -> Result<Vec<abi::Reservation>, abi::Error> {
+        let mut tx = self.pool.begin().await?;
+        let ids = select_series(&mut tx, &selector).await?;
+        let rsvps = sqlx::query_as(
+            r#"
+                WITH updated AS (
+                    UPDATE rsvp.reservations SET status = 'cancelled', cancelled_at = now(),
+                        cancel_reason = $1, expires_at = NULL
+                    WHERE id = ANY($2)
+                    RETURNING *
+                )
+                SELECT * FROM updated ORDER BY lower(timespan), id
+            "#,
+        )
+        .bind(reason)
+        .bind(ids)
+        .fetch_all(&mut tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(rsvps)
+    }
+
+    /// conflicts and capacity are checked row by row, so occurrences are moved
+    /// starting from the one furthest in the direction of the offset. Otherwise an occurrence
+    /// could conflict with the next one of the series before that one is moved as well
+    async fn shift_series(
+        &self,
+        selector: abi::SeriesSelector,
+        offset: chrono::Duration,
+    ) -> Result<Vec<abi::Reservation>, abi::Error> {
+        let mut tx = self.pool.begin().await?;
+        let mut ids = select_series(&mut tx, &selector).await?;
+        if offset > chrono::Duration::zero() {
+            ids.reverse();
+        }
+
+        let mut rsvps = Vec::with_capacity(ids.len());
+        for id in ids {
+            let (start, end): (DateTime<Utc>, DateTime<Utc>) = sqlx::query_as(
+                "SELECT lower(timespan), upper(timespan) FROM rsvp.reservations WHERE id = $1",
+            )
+            .bind(id)
+            .fetch_one(&mut tx)
+            .await?;
+
+            let (Some(start), Some(end)) = (
+                start.checked_add_signed(offset),
+                end.checked_add_signed(offset),
+            ) else {
+                tx.rollback().await?;
+                return Err(abi::Error::InvalidDuration(offset.num_seconds()));
+            };
+            match move_reservation(&mut tx, id, start, end).await {
+                Ok(rsvp) => rsvps.push(rsvp),
+                Err(e) => {
+                    tx.rollback().await?;
+                    return Err(with_conflicting_id(&self.pool, e).await);
+                }
+            }
+        }
+        tx.commit().await?;
+
+        if offset > chrono::Duration::zero() {
+            rsvps.reverse();
+        }
+        Ok(rsvps)
+    }
+
     /// get reservation by id
     async fn get(&self, id: ReservationId) -> Result<abi::Reservation, abi::Error> {
         id.validate()?;
@@ -106,16 +535,31 @@ impl Rsvp for ReservationManager {
 
         Ok(rsvp)
     }
+    async fn get_many(&self, ids: Vec<ReservationId>) -> Result<Vec<BatchResult>, abi::Error> {
+        let refs: Vec<_> = ids.iter().collect();
+        let rsvps = fetch_by_ids(&self.pool, &refs).await?;
+        Ok(ids
+            .iter()
+            .map(|id| {
+                id.validate()?;
+                rsvps.get(id).cloned().ok_or(abi::Error::NotFound)
+            })
+            .collect())
+    }
     /// query reservation
     async fn query(
         &self,
-        query: abi::ReservationQuery,
+        mut query: abi::ReservationQuery,
     ) -> mpsc::Receiver<Result<abi::Reservation, abi::Error>> {
         let pool = self.pool.clone();
         let (tx, rx) = mpsc::channel(128);
         tokio::spawn(async move {
-            let sql = query.to_sql();
-            let mut rsvps = sqlx::query_as(&sql).fetch_many(&pool);
+            if let Err(e) = query.normalize() {
+                let _ = tx.send(Err(e)).await;
+                return;
+            }
+            let mut builder = query.to_sql();
+            let mut rsvps = builder.build_query_as().fetch_many(&pool);
             while let Some(ret) = rsvps.next().await {
                 match ret {
                     Ok(Either::Left(r)) => {
@@ -146,121 +590,1729 @@ impl Rsvp for ReservationManager {
         mut filter: abi::ReservationFilter,
     ) -> Result<(abi::FilterPager, Vec<abi::Reservation>), abi::Error> {
         filter.normalize()?;
-        let sql = filter.to_sql();
-        let rsvps: Vec<abi::Reservation> = sqlx::query_as(&sql).fetch_all(&self.pool).await?;
+        let rsvps: Vec<abi::Reservation> = filter
+            .to_sql()
+            .build_query_as()
+            .fetch_all(&self.pool)
+            .await?;
         let mut data = rsvps.into_iter().collect();
         let pager = filter.get_pager(&mut data);
         Ok((pager, data.into_iter().collect()))
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use abi::{
-        Reservation, ReservationConflict, ReservationConflictInfo, ReservationFilterBuilder,
-        ReservationQueryBuilder, ReservationStatus, ReservationWindow,
-    };
-    use docker_tester::TestPostgres;
-    use prost_types::Timestamp;
-    use sqlx::PgPool;
+    /// listen to reservation changes, resuming from the consumer cursor or last change id if given
+    async fn listen(
+        &self,
+        request: abi::ListenRequest,
+    ) -> mpsc::Receiver<Result<abi::ListenResponse, abi::Error>> {
+        let pool = self.pool.clone();
+        let (tx, rx) = mpsc::channel(128);
+        // subscribe before returning, so that no change made after this call is missed
+        let subscription = subscribe(&pool, &request).await;
+        tokio::spawn(async move {
+            let ret = match subscription {
+                Ok((listener, cursor)) => {
+                    forward_changes(&pool, listener, &request, cursor, &tx).await
+                }
+                Err(e) => Err(e),
+            };
+            if let Err(e) = ret {
+                warn!("Listen error: {:?}", e);
+                let _ = tx.send(Err(e)).await;
+            }
+        });
+        rx
+    }
 
-    use super::*;
+    /// the cursor of the consumer is only moved by acks, so changes sent but not yet handled
+    /// when the consumer disconnects are sent again on resume
+    async fn ack(&self, request: abi::AckRequest) -> Result<(), abi::Error> {
+        request.validate()?;
+        let saved = sqlx::query(
+            r#"
+                INSERT INTO rsvp.server_read_cursor (server_id, last_change_id)
+                SELECT $1, id FROM rsvp.reservation_changes WHERE id = $2
+                ON CONFLICT (server_id) DO UPDATE SET last_change_id = EXCLUDED.last_change_id
+            "#,
+        )
+        .bind(&request.consumer_id)
+        .bind(request.change_id)
+        .execute(&self.pool)
+        .await?;
 
-    #[tokio::test]
-    async fn reserve_should_work_for_valid_window() {
-        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
-        let pool = test_postgres.get_pool().await;
-        let (rsvp, _) = make_user_one_reservation(pool).await;
-        assert!(rsvp.id != 0);
+        if saved.rows_affected() == 0 {
+            return Err(abi::Error::InvalidCursor(request.change_id));
+        }
+        Ok(())
     }
 
-    #[tokio::test]
-    async fn reserve_conflict_reservation_should_reject() {
-        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
-        let pool = test_postgres.get_pool().await;
-        let manager = ReservationManager::new(pool);
-        let rsvp1 = abi::Reservation::new_pending(
-            "user_id_1",
-            "ocean-view-room-713",
-            "2022-12-25T15:00:00-0700".parse().unwrap(),
-            "2022-12-28T12:00:00-0700".parse().unwrap(),
-            "hello I'm user 1.",
-        );
-        let rsvp2 = abi::Reservation::new_pending(
-            "user_id_2",
-            "ocean-view-room-713",
-            "2022-12-26T15:00:00-0700".parse().unwrap(),
-            "2022-12-30T12:00:00-0700".parse().unwrap(),
-            "hello I'm user 2.",
-        );
-        let _rsvp1 = manager.reserve(rsvp1).await.unwrap();
-        let err = manager.reserve(rsvp2).await.unwrap_err();
+    /// change history of a reservation order by change id
+    async fn history(
+        &self,
+        request: abi::HistoryRequest,
+    ) -> Result<(abi::FilterPager, Vec<abi::ReservationChange>), abi::Error> {
+        request.validate()?;
+        let rows: Vec<PgRow> = request.to_sql().build().fetch_all(&self.pool).await?;
+        let mut data = abi::ReservationChange::from_rows(&rows)?
+            .into_iter()
+            .collect();
+        let pager = request.get_pager(&mut data);
+        Ok((pager, data.into_iter().collect()))
+    }
 
-        let info = ReservationConflictInfo::Parsed(ReservationConflict {
-            new: ReservationWindow {
-                rid: "ocean-view-room-713".to_string(),
-                start: "2022-12-26T15:00:00-0700".parse().unwrap(),
-                end: "2022-12-30T12:00:00-0700".parse().unwrap(),
-            },
-            old: ReservationWindow {
-                rid: "ocean-view-room-713".to_string(),
-                start: "2022-12-25T15:00:00-0700".parse().unwrap(),
-                end: "2022-12-28T12:00:00-0700".parse().unwrap(),
-            },
-        });
+    async fn availability(
+        &self,
+        mut request: abi::AvailabilityRequest,
+    ) -> Result<Vec<abi::FreeSlot>, abi::Error> {
+        request.validate()?;
+        if !request.group_id.is_empty() {
+            let members: Vec<String> = sqlx::query_scalar(
+                "SELECT id FROM rsvp.resources WHERE id IN (SELECT rsvp.resource_members($1)) AND active",
+            )
+            .bind(&request.group_id)
+            .fetch_all(&self.pool)
+            .await?;
+            if members.is_empty() {
+                return Err(abi::Error::UnknownResource(request.group_id));
+            }
+            request.resource_ids.extend(members);
+        }
+        request.normalize()?;
+        let busy: Vec<(String, DateTime<Utc>, DateTime<Utc>)> = request
+            .to_sql()
+            .build_query_as()
+            .fetch_all(&self.pool)
+            .await?;
+        // resources are blocked outside of their opening hours and during their blackouts
+        let mut blocked: Vec<(String, DateTime<Utc>, DateTime<Utc>)> = sqlx::query_as(
+            r#"
+                SELECT resource_id, lower(timespan * $2) AS start, upper(timespan * $2) AS end
+                FROM rsvp.resource_blackouts WHERE resource_id = ANY($1) AND timespan && $2
+            "#,
+        )
+        .bind(&request.resource_ids)
+        .bind(request.get_timespan())
+        .fetch_all(&self.pool)
+        .await?;
+        let resources: Vec<abi::Resource> =
+            sqlx::query_as("SELECT * FROM rsvp.resources WHERE id = ANY($1)")
+                .bind(&request.resource_ids)
+                .fetch_all(&self.pool)
+                .await?;
+        let (start, end) = request.window();
+        let mut capacities = HashMap::with_capacity(resources.len());
+        for resource in resources {
+            for (closed, opens) in resource.closed_intervals(start, end)? {
+                blocked.push((resource.id.clone(), closed, opens));
+            }
+            capacities.insert(resource.id, resource.capacity);
+        }
+        Ok(request.free_slots(&busy, &blocked, &capacities))
+    }
+}
 
-        assert_eq!(err, abi::Error::ConflictReservation(info));
+impl ReservationManager {
+    /// only pending reservations hold their slot for a limited time
+    fn hold_ttl_secs_of(&self, rsvp: &abi::Reservation) -> i64 {
+        match abi::ReservationStatus::from_i32(rsvp.status) {
+            Some(abi::ReservationStatus::Pending) | None if rsvp.hold_ttl_secs > 0 => {
+                rsvp.hold_ttl_secs
+            }
+            Some(abi::ReservationStatus::Pending) | None => self.hold_ttl_secs,
+            _ => 0,
+        }
     }
 
-    #[tokio::test]
-    async fn reserve_change_status_should_work() {
-        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
-        let pool = test_postgres.get_pool().await;
-        let (rsvp, manager) = make_user_one_reservation(pool).await;
-        assert!(rsvp.id != 0);
+    /// cancel expired pending reservations, at most HOLD_REAP_BATCH at a time. Rows are locked
+    /// with SKIP LOCKED so that replicas reaping at the same time don't wait on each other
+    pub async fn expire_holds(&self) -> Result<Vec<abi::Reservation>, abi::Error> {
+        let rsvps = sqlx::query_as(
+            r#"
+                UPDATE rsvp.reservations SET status = 'cancelled', cancelled_at = now(), cancel_reason = $1
+                WHERE id IN (
+                    SELECT id FROM rsvp.reservations WHERE status = 'pending' AND expires_at <= now()
+                    ORDER BY expires_at LIMIT $2 FOR UPDATE SKIP LOCKED
+                )
+                RETURNING *
+            "#,
+        )
+        .bind(HOLD_EXPIRED_REASON)
+        .bind(HOLD_REAP_BATCH)
+        .fetch_all(&self.pool)
+        .await?;
 
-        let rsvp = manager.change_status(rsvp.id).await.unwrap();
-        assert_eq!(rsvp.status, abi::ReservationStatus::Confirmed as i32);
+        Ok(rsvps)
     }
 
-    #[tokio::test]
-    async fn reserve_change_status_not_pending_should_do_nothing() {
-        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
-        let pool = test_postgres.get_pool().await;
-        let (rsvp, manager) = make_user_two_reservation(pool).await;
-        assert!(rsvp.id != 0);
-
-        let rsvp = manager.change_status(rsvp.id).await.unwrap();
-        // change status again should do nothing
-        let err = manager.change_status(rsvp.id).await.unwrap_err();
-        assert_eq!(err, abi::Error::NotFound);
+    /// expire pending holds every interval, never returns
+    pub async fn reap_holds(self, interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            loop {
+                match self.expire_holds().await {
+                    Ok(rsvps) => {
+                        if !rsvps.is_empty() {
+                            info!("Expired {} pending reservations", rsvps.len());
+                        }
+                        if rsvps.len() < HOLD_REAP_BATCH as usize {
+                            break;
+                        }
+                    }
+                    Err(e) => {
+                        warn!("Failed to expire pending reservations: {:?}", e);
+                        break;
+                    }
+                }
+            }
+        }
     }
 
-    #[tokio::test]
-    async fn update_note_should_work() {
-        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
-        let pool = test_postgres.get_pool().await;
-        let (rsvp, manager) = make_user_one_reservation(pool).await;
-        let rsvp = manager
-            .update_note(rsvp.id, "hello world".into())
-            .await
-            .unwrap();
-        assert_eq!(rsvp.note, "hello world");
+    async fn update_status(
+        &self,
+        id: ReservationId,
+        status: abi::ReservationStatus,
+        reason: Option<String>,
+        expected_version: Option<i64>,
+    ) -> Result<abi::Reservation, abi::Error> {
+        id.validate()?;
+        self.update_statuses(&[id], status, reason, expected_version, false)
+            .await?
+            .pop()
+            .unwrap()
     }
 
-    #[tokio::test]
-    async fn get_reservation_should_work() {
-        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
-        let pool = test_postgres.get_pool().await;
-        let (new_rsvp, manager) = make_user_one_reservation(pool).await;
-        let get_rsvp = manager.get(new_rsvp.id).await.unwrap();
-        assert_eq!(new_rsvp, get_rsvp);
+    /// insert the reservation in its own transaction
+    async fn insert_one(
+        &self,
+        rsvp: &mut abi::Reservation,
+        idempotency_key: Option<&str>,
+    ) -> Result<(), abi::Error> {
+        let hold_ttl_secs = self.hold_ttl_secs_of(rsvp);
+        let mut tx = self.pool.begin().await?;
+        let resources = lock_resources(&mut tx, std::slice::from_ref(&rsvp.resource_id)).await?;
+        let resource = resources.get(&rsvp.resource_id);
+        insert_reservation(
+            &mut tx,
+            rsvp,
+            resource,
+            hold_ttl_secs,
+            None,
+            idempotency_key,
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// reserve the first candidate resource free for the window, every candidate is tried in its
+    /// own savepoint. Fails with the conflict of every candidate if none is free
+    async fn reserve_first_free(
+        &self,
+        mut rsvp: abi::Reservation,
+        candidates: &[String],
+        idempotency_key: &str,
+    ) -> Result<abi::Reservation, abi::Error> {
+        rsvp.resource_id = candidates[0].clone();
+        rsvp.validate()?;
+        if idempotency_key.len() > MAX_IDEMPOTENCY_KEY_LEN {
+            return Err(abi::Error::InvalidIdempotencyKey(idempotency_key.into()));
+        }
+        let key = (!idempotency_key.is_empty()).then_some(idempotency_key);
+        if let Some(key) = key {
+            if let Some(rsvp) = self.get_by_key(&rsvp.user_id, key).await? {
+                return Ok(rsvp);
+            }
+        }
+
+        let hold_ttl_secs = self.hold_ttl_secs_of(&rsvp);
+        let mut tx = self.pool.begin().await?;
+        let resources = lock_resources(&mut tx, candidates).await?;
+        let mut conflicts = Vec::new();
+        for resource_id in candidates {
+            rsvp.resource_id = resource_id.clone();
+            let resource = resources.get(resource_id);
+            let mut savepoint = tx.begin().await?;
+            match insert_reservation(
+                &mut savepoint,
+                &mut rsvp,
+                resource,
+                hold_ttl_secs,
+                None,
+                key,
+            )
+            .await
+            {
+                Ok(()) => {
+                    savepoint.commit().await?;
+                    tx.commit().await?;
+                    return Ok(rsvp);
+                }
+                Err(e @ abi::Error::ConflictReservation(_)) => {
+                    savepoint.rollback().await?;
+                    conflicts.push(e);
+                }
+                // a closed candidate is skipped like a taken one
+                Err(e @ (abi::Error::ResourceClosed(..) | abi::Error::ResourceBlackedOut(..))) => {
+                    savepoint.rollback().await?;
+                    conflicts.push(unavailable(&rsvp, e));
+                }
+                Err(e) => {
+                    savepoint.rollback().await?;
+                    tx.rollback().await?;
+                    // a retry racing with its first attempt fails on the idempotency key
+                    if let Some(key) = key {
+                        if let Some(rsvp) = self.get_by_key(&rsvp.user_id, key).await? {
+                            return Ok(rsvp);
+                        }
+                    }
+                    return Err(e);
+                }
+            }
+        }
+
+        tx.rollback().await?;
+        Err(with_conflicting_ids(&self.pool, conflicts).await)
+    }
+
+    /// reservation made by the user with the idempotency key
+    async fn get_by_key(
+        &self,
+        user_id: &str,
+        idempotency_key: &str,
+    ) -> Result<Option<abi::Reservation>, abi::Error> {
+        let rsvp = sqlx::query_as(
+            "SELECT * FROM rsvp.reservations WHERE user_id = $1 AND idempotency_key = $2",
+        )
+        .bind(user_id)
+        .bind(idempotency_key)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(rsvp)
+    }
+
+    /// the current status is checked in the UPDATE, so concurrent transitions can't race.
+    /// cancellation time and reason are only kept for the cancelled status, and only pending
+    /// reservations keep their expiry. A pending hold past its expiry can't transition anymore,
+    /// it is left to the reaper. If expected_version is given, only the reservations at
+    /// that version are updated. Results are in the order of the ids, in transactional mode
+    /// nothing is updated if any of them fails
+    async fn update_statuses(
+        &self,
+        ids: &[ReservationId],
+        status: abi::ReservationStatus,
+        reason: Option<String>,
+        expected_version: Option<i64>,
+        transactional: bool,
+    ) -> Result<Vec<BatchResult>, abi::Error> {
+        let sources = status.transition_sources();
+        if sources.is_empty() {
+            return Err(abi::Error::InvalidStatus(status as i32));
+        }
+
+        let mut tx = self.pool.begin().await?;
+        let updated: Vec<abi::Reservation> = sqlx::query_as(
+            r#"
+                UPDATE rsvp.reservations SET status = $1::rsvp.reservation_status,
+                    cancelled_at = CASE WHEN $1 = 'cancelled' THEN now() END, cancel_reason = $4,
+                    expires_at = CASE WHEN $1 = 'pending' THEN expires_at END
+                WHERE id = ANY($2) AND status = ANY($3::rsvp.reservation_status[])
+                    AND ($5::bigint IS NULL OR version = $5)
+                    AND NOT (status = 'pending' AND expires_at IS NOT NULL AND expires_at <= now())
+                RETURNING *
+            "#,
+        )
+        .bind(status.to_string())
+        .bind(ids)
+        .bind(sources.iter().map(|s| s.to_string()).collect::<Vec<_>>())
+        .bind(reason)
+        .bind(expected_version)
+        .fetch_all(&mut tx)
+        .await?;
+
+        // the others either don't exist, are at another version, are expired holds or their
+        // status can't transition
+        let updated: HashMap<_, _> = updated.into_iter().map(|r| (r.id, r)).collect();
+        let missing: Vec<_> = ids.iter().filter(|id| !updated.contains_key(id)).collect();
+        let current = fetch_by_ids(&mut tx, &missing).await?;
+
+        let results = ids
+            .iter()
+            .map(|id| {
+                id.validate()?;
+                match (updated.get(id), current.get(id)) {
+                    (Some(rsvp), _) => Ok(rsvp.clone()),
+                    (None, Some(rsvp)) => match expected_version {
+                        Some(expected) if expected != rsvp.version => {
+                            Err(abi::Error::VersionMismatch(expected, rsvp.version))
+                        }
+                        _ if sources.contains(&rsvp.status()) && is_expired_hold(rsvp) => {
+                            Err(abi::Error::HoldExpired(rsvp.id))
+                        }
+                        _ => Err(abi::Error::IllegalTransition(rsvp.status(), status)),
+                    },
+                    (None, None) => Err(abi::Error::NotFound),
+                }
+            })
+            .collect();
+        finish_batch(tx, results, transactional).await
+    }
+}
+
+/// commit the batch, or roll it back in transactional mode if any result is an error.
+/// The successful results of a rolled back batch become Aborted
+async fn finish_batch(
+    tx: Transaction<'_, Postgres>,
+    mut results: Vec<BatchResult>,
+    transactional: bool,
+) -> Result<Vec<BatchResult>, abi::Error> {
+    if !transactional || results.iter().all(|r| r.is_ok()) {
+        tx.commit().await?;
+        return Ok(results);
+    }
+
+    tx.rollback().await?;
+    for result in results.iter_mut().filter(|r| r.is_ok()) {
+        *result = Err(abi::Error::Aborted);
+    }
+    Ok(results)
+}
+
+/// reservations by id, ids not found are missing from the map
+async fn fetch_by_ids<'c>(
+    executor: impl PgExecutor<'c>,
+    ids: &[&ReservationId],
+) -> Result<HashMap<ReservationId, abi::Reservation>, abi::Error> {
+    if ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let rsvps: Vec<abi::Reservation> =
+        sqlx::query_as("SELECT * FROM rsvp.reservations WHERE id = ANY($1)")
+            .bind(ids.iter().map(|id| **id).collect::<Vec<_>>())
+            .fetch_all(executor)
+            .await?;
+    Ok(rsvps.into_iter().map(|r| (r.id, r)).collect())
+}
+
+/// insert the reservation, fill in the generated id and expiry. Nothing is inserted if the
+/// resource is not in the catalog, is inactive, is closed or is full. The resource must come from
+/// lock_resources in the same transaction
+async fn insert_reservation(
+    conn: &mut PgConnection,
+    rsvp: &mut abi::Reservation,
+    resource: Option<&abi::Resource>,
+    hold_ttl_secs: i64,
+    series_id: Option<Uuid>,
+    idempotency_key: Option<&str>,
+) -> Result<(), abi::Error> {
+    let status =
+        abi::ReservationStatus::from_i32(rsvp.status).unwrap_or(abi::ReservationStatus::Pending);
+
+    let capacity = match resource {
+        Some(resource) if resource.active => {
+            let window = window_of(rsvp);
+            check_calendar(&mut *conn, resource, &window).await?;
+            check_capacity(&mut *conn, &window, resource.capacity, None).await?;
+            resource.capacity
+        }
+        _ => return Err(abi::Error::UnknownResource(rsvp.resource_id.clone())),
+    };
+
+    let timespan: PgRange<DateTime<Utc>> = rsvp.get_timespan();
+
+    // generate a insert sql for the reservation
+    // execute the sql
+    let row = sqlx::query(
+        r#"
+            INSERT INTO rsvp.reservations (user_id, resource_id, timespan, note, status, expires_at, series_id, idempotency_key, pooled)
+            VALUES ($1, $2, $3, $4, $5::rsvp.reservation_status,
+                CASE WHEN $6 > 0 THEN now() + $6 * interval '1 second' END, $7, $8, $9)
+            RETURNING id, expires_at, version
+        "#,
+    )
+    .bind(rsvp.user_id.clone())
+    .bind(rsvp.resource_id.clone())
+    .bind(timespan)
+    .bind(rsvp.note.clone())
+    .bind(status.to_string())
+    .bind(hold_ttl_secs)
+    .bind(series_id)
+    .bind(idempotency_key)
+    .bind(capacity > 1)
+    .fetch_one(conn)
+    .await?;
+
+    rsvp.id = row.get("id");
+    rsvp.version = row.get("version");
+    rsvp.expires_at = row
+        .get::<Option<DateTime<Utc>>, _>("expires_at")
+        .as_ref()
+        .map(abi::convert_to_timestamp);
+    Ok(())
+}
+
+/// move the reservation to the new timespan, checking the calendar and capacity of its resource.
+/// Resources removed from the catalog since the reservation was made have a capacity of 1.
+/// Cancelled reservations no longer hold their timespan, so they can't be moved
+async fn move_reservation(
+    conn: &mut PgConnection,
+    id: ReservationId,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<abi::Reservation, abi::Error> {
+    let (rid, cancelled): (String, bool) = sqlx::query_as(
+        "SELECT resource_id, status = 'cancelled' FROM rsvp.reservations WHERE id = $1 FOR UPDATE",
+    )
+    .bind(id)
+    .fetch_optional(&mut *conn)
+    .await?
+    .ok_or(abi::Error::NotFound)?;
+    if cancelled {
+        return Err(abi::Error::ReservationCancelled(id));
+    }
+    let resource = lock_resources(&mut *conn, std::slice::from_ref(&rid))
+        .await?
+        .remove(&rid);
+    let window = abi::ReservationWindow { rid, start, end };
+    if let Some(resource) = resource.as_ref() {
+        check_calendar(&mut *conn, resource, &window).await?;
+    }
+    let capacity = resource.map_or(1, |resource| resource.capacity);
+    check_capacity(&mut *conn, &window, capacity, Some(id)).await?;
+
+    let rsvp = sqlx::query_as(
+        r#"
+            UPDATE rsvp.reservations SET timespan = $1 WHERE id = $2
+            RETURNING *
+        "#,
+    )
+    .bind(timespan_of(&window))
+    .bind(id)
+    .fetch_one(conn)
+    .await?;
+
+    Ok(rsvp)
+}
+
+/// the resources in the catalog by id. Rows of resources with capacity above 1 are locked until
+/// the transaction ends, so that their capacity checks are serialized. They are locked at once in
+/// id order, so that transactions reserving several resources can't deadlock. Overlaps on the
+/// other resources are rejected by the reservations_conflict constraint, so they are not locked
+async fn lock_resources(
+    conn: &mut PgConnection,
+    rids: &[String],
+) -> Result<HashMap<String, abi::Resource>, abi::Error> {
+    let resources: Vec<abi::Resource> =
+        sqlx::query_as("SELECT * FROM rsvp.resources WHERE id = ANY($1)")
+            .bind(rids)
+            .fetch_all(&mut *conn)
+            .await?;
+    let pooled: Vec<String> = resources
+        .iter()
+        .filter(|r| r.capacity > 1)
+        .map(|r| r.id.clone())
+        .collect();
+    let mut resources: HashMap<_, _> = resources.into_iter().map(|r| (r.id.clone(), r)).collect();
+    if pooled.is_empty() {
+        return Ok(resources);
+    }
+
+    // read again under the lock, the capacity could have been changed meanwhile
+    let locked: Vec<abi::Resource> =
+        sqlx::query_as("SELECT * FROM rsvp.resources WHERE id = ANY($1) ORDER BY id FOR UPDATE")
+            .bind(&pooled)
+            .fetch_all(conn)
+            .await?;
+    for resource in locked {
+        resources.insert(resource.id.clone(), resource);
+    }
+    Ok(resources)
+}
+
+/// the window must be within the opening hours of the resource and must not overlap any of
+/// its blackouts
+async fn check_calendar(
+    conn: &mut PgConnection,
+    resource: &abi::Resource,
+    window: &abi::ReservationWindow,
+) -> Result<(), abi::Error> {
+    resource.check_open(window.start, window.end)?;
+
+    let blackout: Option<abi::Blackout> = sqlx::query_as(
+        r#"
+            SELECT * FROM rsvp.resource_blackouts WHERE resource_id = $1 AND timespan && $2
+            ORDER BY lower(timespan) LIMIT 1
+        "#,
+    )
+    .bind(&resource.id)
+    .bind(timespan_of(window))
+    .fetch_optional(conn)
+    .await?;
+
+    match blackout {
+        Some(blackout) => Err(blackout.into()),
+        None => Ok(()),
+    }
+}
+
+/// overlaps on a resource with capacity 1 are rejected by the reservations_conflict constraint.
+/// Otherwise fail with the first interval of the window in which `capacity` reservations
+/// overlap already, reservation `exclude` is not counted
+async fn check_capacity(
+    conn: &mut PgConnection,
+    window: &abi::ReservationWindow,
+    capacity: i32,
+    exclude: Option<ReservationId>,
+) -> Result<(), abi::Error> {
+    if capacity <= 1 {
+        return Ok(());
+    }
+
+    let full =
+        find_full_interval(conn, &window.rid, timespan_of(window), capacity, exclude).await?;
+    match full {
+        None => Ok(()),
+        Some((start, end)) => Err(abi::Error::ConflictReservation(
+            ReservationConflictInfo::Full(abi::ResourceFull {
+                new: window.clone(),
+                full: abi::ReservationWindow {
+                    rid: window.rid.clone(),
+                    start,
+                    end,
+                },
+                capacity,
+            }),
+        )),
+    }
+}
+
+/// first interval of the span in which at least `count` reservations of the resource overlap,
+/// reservation `exclude` is not counted
+pub(crate) async fn find_full_interval(
+    conn: &mut PgConnection,
+    rid: &str,
+    span: PgRange<DateTime<Utc>>,
+    count: i32,
+    exclude: Option<ReservationId>,
+) -> Result<Option<(DateTime<Utc>, DateTime<Utc>)>, abi::Error> {
+    // the count only grows at the start of a reservation, so the first full interval starts
+    // at one of them and lasts until one of the reservations counted there ends
+    let full = sqlx::query_as(
+        r#"
+            WITH overlapping AS (
+                SELECT timespan * $2 AS span FROM rsvp.reservations
+                WHERE resource_id = $1 AND timespan && $2 AND status <> 'cancelled'
+                    AND id IS DISTINCT FROM $4
+            )
+            SELECT p.start, MIN(upper(o.span))
+            FROM (SELECT DISTINCT lower(span) AS start FROM overlapping) p
+            JOIN overlapping o ON o.span @> p.start
+            GROUP BY p.start HAVING COUNT(*) >= $3
+            ORDER BY p.start LIMIT 1
+        "#,
+    )
+    .bind(rid)
+    .bind(span)
+    .bind(count)
+    .bind(exclude)
+    .fetch_optional(conn)
+    .await?;
+
+    Ok(full)
+}
+
+fn timespan_of(window: &abi::ReservationWindow) -> PgRange<DateTime<Utc>> {
+    PgRange {
+        start: Bound::Included(window.start),
+        end: Bound::Excluded(window.end),
+    }
+}
+
+fn window_of(rsvp: &abi::Reservation) -> abi::ReservationWindow {
+    abi::ReservationWindow {
+        rid: rsvp.resource_id.clone(),
+        start: abi::convert_to_utc_time(rsvp.start.as_ref().unwrap()),
+        end: abi::convert_to_utc_time(rsvp.end.as_ref().unwrap()),
+    }
+}
+
+/// pending reservation whose hold has run out, but the reaper hasn't cancelled yet
+fn is_expired_hold(rsvp: &abi::Reservation) -> bool {
+    rsvp.status() == abi::ReservationStatus::Pending
+        && rsvp
+            .expires_at
+            .as_ref()
+            .is_some_and(|t| abi::convert_to_utc_time(t) <= Utc::now())
+}
+
+/// conflict for a reservation the resource can't take, with the error as the reason
+fn unavailable(rsvp: &abi::Reservation, e: abi::Error) -> abi::Error {
+    let info = abi::ResourceUnavailable {
+        new: window_of(rsvp),
+        reason: e.to_string(),
+    };
+    abi::Error::ConflictReservation(ReservationConflictInfo::Unavailable(info))
+}
+
+/// ids of the occurrences selected by the selector order by start time, the rows are locked
+/// until the transaction ends
+async fn select_series<'c>(
+    executor: impl PgExecutor<'c>,
+    selector: &abi::SeriesSelector,
+) -> Result<Vec<ReservationId>, abi::Error> {
+    selector.validate()?;
+    let ids: Vec<(ReservationId,)> = selector
+        .to_sql()
+        .build_query_as()
+        .fetch_all(executor)
+        .await?;
+    if ids.is_empty() {
+        return Err(abi::Error::NotFound);
+    }
+
+    Ok(ids.into_iter().map(|(id,)| id).collect())
+}
+
+/// the exclusion violation only reports the conflicting windows, look up the id of the existing
+/// reservation so that clients don't have to. If the lookup fails its error is returned instead
+async fn with_conflicting_id<'c>(executor: impl PgExecutor<'c>, err: abi::Error) -> abi::Error {
+    let abi::Error::ConflictReservation(ReservationConflictInfo::Parsed(mut conflict)) = err else {
+        return err;
+    };
+
+    let old = &conflict.old;
+    let found = sqlx::query_scalar(
+        "SELECT id FROM rsvp.reservations WHERE resource_id = $1 AND timespan = tstzrange($2, $3) AND status <> 'cancelled'",
+    )
+    .bind(&old.rid)
+    .bind(old.start)
+    .bind(old.end)
+    .fetch_optional(executor)
+    .await;
+    conflict.conflicting_id = match found {
+        // the conflicting reservation may be gone by now
+        Ok(id) => id.unwrap_or_default(),
+        Err(e) => return e.into(),
+    };
+
+    abi::Error::ConflictReservation(ReservationConflictInfo::Parsed(conflict))
+}
+
+/// ConflictReservations with the conflicting ids of the ConflictReservation errors, or the
+/// error of the first lookup that fails
+async fn with_conflicting_ids(pool: &PgPool, conflicts: Vec<abi::Error>) -> abi::Error {
+    let mut infos = Vec::with_capacity(conflicts.len());
+    for e in conflicts {
+        match with_conflicting_id(pool, e).await {
+            abi::Error::ConflictReservation(info) => infos.push(info),
+            e => return e,
+        }
+    }
+    abi::Error::ConflictReservations(infos)
+}
+
+/// listen on CHANGE_CHANNEL and return the listener with the id of the change to start after.
+/// last_change_id takes precedence over the consumer cursor, if neither is available start
+/// after the latest change that is not held back
+async fn subscribe(
+    pool: &PgPool,
+    request: &abi::ListenRequest,
+) -> Result<(PgListener, i64), abi::Error> {
+    request.validate()?;
+    let mut listener = PgListener::connect_with(pool).await?;
+    listener.listen(CHANGE_CHANNEL).await?;
+
+    let mut cursor = request.last_change_id;
+    if let (None, Some(consumer_id)) = (cursor, request.consumer_id.as_ref()) {
+        cursor = sqlx::query_scalar(
+            "SELECT last_change_id FROM rsvp.server_read_cursor WHERE server_id = $1",
+        )
+        .bind(consumer_id)
+        .fetch_optional(pool)
+        .await?;
+    }
+
+    let cursor = match cursor {
+        // 0 replays the whole log, any other cursor has to be a change, otherwise
+        // everything after it would be replayed as well
+        Some(cursor) if cursor > 0 => {
+            let found: Option<i64> =
+                sqlx::query_scalar("SELECT id::int8 FROM rsvp.reservation_changes WHERE id = $1")
+                    .bind(cursor)
+                    .fetch_optional(pool)
+                    .await?;
+            found.ok_or(abi::Error::InvalidCursor(cursor))?
+        }
+        Some(cursor) => cursor,
+        None => {
+            sqlx::query_scalar(
+                r#"
+                    SELECT COALESCE((
+                        SELECT id FROM rsvp.reservation_changes
+                        WHERE xid < pg_snapshot_xmin(pg_current_snapshot())
+                        ORDER BY xid DESC, id DESC LIMIT 1
+                    ), 0)::int8
+                "#,
+            )
+            .fetch_one(pool)
+            .await?
+        }
+    };
+
+    Ok((listener, cursor))
+}
+
+/// send the rows of rsvp.reservation_changes after cursor which match the request filters to tx,
+/// then wait for the next notification, until the receiver is dropped. Changes are sent in
+/// (xid, id) order, those of transactions not older than the oldest one in flight are held back
+/// until it ends, since a change before them could still be committed
+async fn forward_changes(
+    pool: &PgPool,
+    mut listener: PgListener,
+    request: &abi::ListenRequest,
+    mut cursor: i64,
+    tx: &ChangeSender,
+) -> Result<(), abi::Error> {
+    loop {
+        // notifications carry no payload and may be coalesced, so read everything after cursor
+        let rows: Vec<PgRow> = sqlx::query(
+            r#"
+                SELECT c.id::int8 AS change_id, c.op, r.*,
+                    ($2 = '' OR r.resource_id IN (SELECT rsvp.resource_members($2))) AS in_group,
+                    c.xid < pg_snapshot_xmin(pg_current_snapshot()) AS settled
+                FROM rsvp.reservation_changes c,
+                    jsonb_populate_record(NULL::rsvp.reservations, COALESCE(c.new, c.old)) r
+                WHERE (c.xid, c.id) > (
+                    SELECT COALESCE(MAX(xid), '0'::xid8), $1 FROM rsvp.reservation_changes WHERE id = $1
+                )
+                ORDER BY c.xid, c.id
+            "#,
+        )
+        .bind(cursor)
+        .bind(&request.group_id)
+        .fetch_all(pool)
+        .await?;
+
+        let mut held_back = false;
+        for row in rows {
+            if !row.get::<bool, _>("settled") {
+                held_back = true;
+                break;
+            }
+            let change = abi::ListenResponse::from_row(&row)?;
+            let change_id = change.change_id;
+            if !row.get::<bool, _>("in_group") || !request.matches(&change) {
+                cursor = change_id;
+                continue;
+            }
+            info!("Reservation change: {:?}", change);
+            if tx.send(Ok(change)).await.is_err() {
+                // rx is dropped, so client disconnected.
+                return Ok(());
+            }
+            cursor = change_id;
+        }
+
+        tokio::select! {
+            _ = tx.closed() => return Ok(()),
+            notification = listener.recv() => {
+                notification?;
+            }
+            // the transaction holding changes back may end without a notification
+            _ = tokio::time::sleep(HELD_BACK_RETRY), if held_back => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use abi::{
+        AckRequest, AvailabilityRequestBuilder, FreeSlot, HistoryRequestBuilder, ListenRequest,
+        RecurrenceMode, Reservation, ReservationConflict, ReservationConflictInfo,
+        ReservationFilterBuilder, ReservationQueryBuilder, ReservationQueryMode, ReservationStatus,
+        ReservationUpdateType, ReservationWindow, SeriesScope, SeriesSelector,
+    };
+    use docker_tester::TestPostgres;
+    use prost_types::Timestamp;
+    use sqlx::PgPool;
+
+    use super::*;
+    use crate::ResourceCatalog;
+
+    #[tokio::test]
+    async fn reserve_should_work_for_valid_window() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let pool = get_pool(&test_postgres).await;
+        let (rsvp, _) = make_user_one_reservation(pool).await;
+        assert!(rsvp.id != 0);
+    }
+
+    #[tokio::test]
+    async fn reserve_recurring_best_effort_should_skip_conflicts() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let pool = get_pool(&test_postgres).await;
+        let (blocker, manager) = make_reservation(
+            pool.clone(),
+            "user_id_2",
+            "lab-1",
+            "2022-12-06T09:00:00-0800",
+            "2022-12-06T10:00:00-0800",
+            "blocker",
+        )
+        .await;
+
+        let (series_id, occurrences) = manager
+            .reserve_recurring(make_recurring_request(RecurrenceMode::BestEffort))
+            .await
+            .unwrap();
+        assert!(!series_id.is_empty());
+        assert_eq!(occurrences.len(), 3);
+        let conflict = occurrences[1].conflict.as_ref().unwrap();
+        assert_eq!(conflict.conflicting_id, blocker.id);
+        assert_eq!(occurrences[1].reservation.as_ref().unwrap().id, 0);
+
+        for i in [0, 2] {
+            assert!(occurrences[i].conflict.is_none());
+            let rsvp = occurrences[i].reservation.as_ref().unwrap();
+            assert_eq!(rsvp.series_id, series_id);
+            assert_eq!(manager.get(rsvp.id).await.unwrap(), *rsvp);
+        }
+    }
+
+    #[tokio::test]
+    async fn reserve_recurring_best_effort_should_skip_blacked_out_occurrences() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let manager = ReservationManager::new(get_pool(&test_postgres).await);
+        manager
+            .add_blackout(abi::Blackout::new(
+                "lab-1",
+                "2022-12-06T00:00:00-0800".parse().unwrap(),
+                "2022-12-07T00:00:00-0800".parse().unwrap(),
+                "maintenance",
+            ))
+            .await
+            .unwrap();
+
+        let (series_id, occurrences) = manager
+            .reserve_recurring(make_recurring_request(RecurrenceMode::BestEffort))
+            .await
+            .unwrap();
+        assert!(!series_id.is_empty());
+        let conflict = occurrences[1].conflict.as_ref().unwrap();
+        assert_eq!(conflict.code, abi::ErrorCode::ResourceUnavailable as i32);
+        assert!(
+            conflict.reason.contains("maintenance"),
+            "{}",
+            conflict.reason
+        );
+        assert_eq!(occurrences[1].reservation.as_ref().unwrap().id, 0);
+        assert!(occurrences[0].conflict.is_none());
+        assert!(occurrences[2].conflict.is_none());
+    }
+
+    #[tokio::test]
+    async fn reserve_recurring_all_or_nothing_should_reserve_nothing_on_conflict() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let pool = get_pool(&test_postgres).await;
+        let manager = ReservationManager::new(pool.clone());
+
+        let (series_id, occurrences) = manager
+            .reserve_recurring(make_recurring_request(RecurrenceMode::AllOrNothing))
+            .await
+            .unwrap();
+        assert!(!series_id.is_empty());
+        assert!(occurrences.iter().all(|o| o.conflict.is_none()));
+
+        // the same series again conflicts with every occurrence
+        let (series_id, occurrences) = manager
+            .reserve_recurring(make_recurring_request(RecurrenceMode::AllOrNothing))
+            .await
+            .unwrap();
+        assert!(series_id.is_empty());
+        assert!(occurrences.iter().all(|o| o.conflict.is_some()));
+
+        let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM rsvp.reservations")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(count, 3);
+    }
+
+    #[tokio::test]
+    async fn series_update_and_cancel_should_follow_scope() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let manager = ReservationManager::new(get_pool(&test_postgres).await);
+        let (series_id, occurrences) = manager
+            .reserve_recurring(make_recurring_request(RecurrenceMode::AllOrNothing))
+            .await
+            .unwrap();
+        let ids: Vec<_> = occurrences
+            .iter()
+            .map(|o| o.reservation.as_ref().unwrap().id)
+            .collect();
+
+        let selector = SeriesSelector::new(&series_id, ids[1], SeriesScope::ThisAndFollowing);
+        let rsvps = manager
+            .update_series_note(selector, "moved to lab-2".into())
+            .await
+            .unwrap();
+        assert_eq!(rsvps.iter().map(|r| r.id).collect::<Vec<_>>(), ids[1..]);
+        assert!(rsvps.iter().all(|r| r.note == "moved to lab-2"));
+        assert_eq!(manager.get(ids[0]).await.unwrap().note, "daily lab slot");
+
+        let selector = SeriesSelector::new(&series_id, ids[1], SeriesScope::This);
+        let rsvps = manager
+            .cancel_series(selector, "holiday".into())
+            .await
+            .unwrap();
+        assert_eq!(rsvps.len(), 1);
+        assert_eq!(rsvps[0].status, ReservationStatus::Cancelled as i32);
+        assert_eq!(rsvps[0].cancel_reason, "holiday");
+
+        // cancelled occurrences are not selected again
+        let selector = SeriesSelector::new(&series_id, 0, SeriesScope::All);
+        let rsvps = manager
+            .cancel_series(selector.clone(), "".into())
+            .await
+            .unwrap();
+        assert_eq!(
+            rsvps.iter().map(|r| r.id).collect::<Vec<_>>(),
+            [ids[0], ids[2]]
+        );
+        let err = manager
+            .cancel_series(selector, "".into())
+            .await
+            .unwrap_err();
+        assert_eq!(err, abi::Error::NotFound);
+    }
+
+    #[tokio::test]
+    async fn shift_series_should_move_occurrences_or_nothing() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let manager = ReservationManager::new(get_pool(&test_postgres).await);
+        let (series_id, occurrences) = manager
+            .reserve_recurring(make_recurring_request(RecurrenceMode::AllOrNothing))
+            .await
+            .unwrap();
+        let first = occurrences[0].reservation.clone().unwrap();
+        let selector = SeriesSelector::new(&series_id, 0, SeriesScope::All);
+
+        // daily occurrences moved by a day take each other's place
+        let day = chrono::Duration::days(1);
+        let rsvps = manager.shift_series(selector.clone(), day).await.unwrap();
+        assert_eq!(rsvps.len(), 3);
+        assert_eq!(rsvps[0].id, first.id);
+        assert_eq!(
+            rsvps[0].start,
+            occurrences[1].reservation.as_ref().unwrap().start
+        );
+        let rsvps = manager.shift_series(selector.clone(), -day).await.unwrap();
+        assert_eq!(
+            rsvps[0],
+            Reservation {
+                version: 3,
+                ..first
+            }
+        );
+        let first = rsvps[0].clone();
+
+        let (blocker, _) = make_reservation(
+            manager.pool.clone(),
+            "user_id_2",
+            "lab-1",
+            "2022-12-08T10:00:00-0800",
+            "2022-12-08T12:00:00-0800",
+            "blocker",
+        )
+        .await;
+        let err = manager
+            .shift_series(selector.clone(), day)
+            .await
+            .unwrap_err();
+        let abi::Error::ConflictReservation(ReservationConflictInfo::Parsed(conflict)) = err else {
+            panic!("expect conflict reservation error");
+        };
+        assert_eq!(conflict.conflicting_id, Some(blocker.id));
+        assert_eq!(manager.get(first.id).await.unwrap(), first);
+
+        let offset = chrono::Duration::try_seconds(i64::MAX / 1000).unwrap();
+        let err = manager.shift_series(selector, offset).await.unwrap_err();
+        assert_eq!(err, abi::Error::InvalidDuration(i64::MAX / 1000));
+        assert_eq!(manager.get(first.id).await.unwrap(), first);
+    }
+
+    #[tokio::test]
+    async fn reserve_many_in_opposite_orders_should_not_deadlock() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let manager = ReservationManager::new(get_pool(&test_postgres).await);
+        for id in ["desk-pool-1", "desk-pool-2"] {
+            manager
+                .create_resource(abi::Resource::new(id, id, "desk").with_capacity(2))
+                .await
+                .unwrap();
+        }
+        let desks = |rids: [&str; 2], hour: u32| {
+            rids.map(|rid| {
+                abi::Reservation::new_pending(
+                    "user_id_1",
+                    rid,
+                    format!("2022-12-20T{:02}:00:00Z", hour).parse().unwrap(),
+                    format!("2022-12-20T{:02}:30:00Z", hour).parse().unwrap(),
+                    "desk",
+                )
+            })
+            .to_vec()
+        };
+
+        for hour in 0..20 {
+            let (r1, r2) = tokio::join!(
+                manager.reserve_many(desks(["desk-pool-1", "desk-pool-2"], hour)),
+                manager.reserve_many(desks(["desk-pool-2", "desk-pool-1"], hour)),
+            );
+            r1.unwrap();
+            r2.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn reserve_many_should_reserve_all_or_report_every_conflict() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let pool = get_pool(&test_postgres).await;
+        let manager = ReservationManager::new(pool.clone());
+        let meeting = |rid: &str| {
+            abi::Reservation::new_pending(
+                "user_id_1",
+                rid,
+                "2022-12-26T15:00:00-0700".parse().unwrap(),
+                "2022-12-26T16:00:00-0700".parse().unwrap(),
+                "weekly sync",
+            )
+        };
+
+        let rsvps = manager
+            .reserve_many(vec![meeting("room-1"), meeting("projector-1")])
+            .await
+            .unwrap();
+        assert_eq!(rsvps.len(), 2);
+        assert!(rsvps.iter().all(|r| r.id != 0));
+        assert_eq!(rsvps[1].resource_id, "projector-1");
+
+        let err = manager
+            .reserve_many(vec![
+                meeting("room-1"),
+                meeting("bridge-1"),
+                meeting("projector-1"),
+            ])
+            .await
+            .unwrap_err();
+        let abi::Error::ConflictReservations(infos) = err else {
+            panic!("expect conflict reservations error");
+        };
+        let ids: Vec<_> = infos
+            .iter()
+            .map(|info| match info {
+                ReservationConflictInfo::Parsed(conflict) => conflict.conflicting_id,
+                _ => None,
+            })
+            .collect();
+        assert_eq!(ids, [Some(rsvps[0].id), Some(rsvps[1].id)]);
+
+        // bridge-1 didn't conflict but is rolled back as well
+        let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM rsvp.reservations")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(count, 2);
+    }
+
+    #[tokio::test]
+    async fn reserve_any_should_pick_the_first_free_resource() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let pool = get_pool(&test_postgres).await;
+        let manager = ReservationManager::new(pool);
+        let meeting = abi::Reservation::new_pending(
+            "user_id_1",
+            "",
+            "2022-12-26T15:00:00-0700".parse().unwrap(),
+            "2022-12-26T16:00:00-0700".parse().unwrap(),
+            "weekly sync",
+        );
+        let rooms = vec![
+            "floor-3-room-301".to_string(),
+            "floor-3-room-302".to_string(),
+            "floor-3-room-301".to_string(),
+        ];
+
+        let mut chosen = Vec::new();
+        for _ in 0..2 {
+            let rsvp = manager
+                .reserve_any(meeting.clone(), rooms.clone(), "".into())
+                .await
+                .unwrap();
+            assert!(rsvp.id > 0);
+            chosen.push(rsvp);
+        }
+        assert_eq!(chosen[0].resource_id, "floor-3-room-301");
+        assert_eq!(chosen[1].resource_id, "floor-3-room-302");
+
+        // every room is taken, duplicated candidates are reported once
+        let err = manager
+            .reserve_any(meeting.clone(), rooms, "".into())
+            .await
+            .unwrap_err();
+        let abi::Error::ConflictReservations(infos) = err else {
+            panic!("expect conflict reservations error");
+        };
+        let ids: Vec<_> = infos
+            .iter()
+            .map(|info| match info {
+                ReservationConflictInfo::Parsed(conflict) => conflict.conflicting_id,
+                _ => None,
+            })
+            .collect();
+        assert_eq!(ids, [Some(chosen[0].id), Some(chosen[1].id)]);
+
+        let err = manager
+            .reserve_any(meeting, vec![], "".into())
+            .await
+            .unwrap_err();
+        assert_eq!(err, abi::Error::InvalidResourceId("".into()));
+    }
+
+    #[tokio::test]
+    async fn confirm_many_should_report_every_id() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let (rsvp1, manager) = make_user_one_reservation(get_pool(&test_postgres).await).await;
+        let (rsvp2, _) = make_reservation(
+            manager.pool.clone(),
+            "user_id_2",
+            "lab-1",
+            "2022-12-26T15:00:00-0700",
+            "2022-12-30T12:00:00-0700",
+            "hello I'm user 2.",
+        )
+        .await;
+        manager.change_status(rsvp2.id, None).await.unwrap();
+
+        // transactional: rsvp1 is not confirmed since rsvp2 already is
+        let results = manager
+            .confirm_many(vec![rsvp1.id, rsvp2.id, 0, 10000], true)
+            .await
+            .unwrap();
+        assert_eq!(
+            results,
+            vec![
+                Err(abi::Error::Aborted),
+                Err(abi::Error::IllegalTransition(
+                    ReservationStatus::Confirmed,
+                    ReservationStatus::Confirmed
+                )),
+                Err(abi::Error::InvalidReservationId(0)),
+                Err(abi::Error::NotFound),
+            ]
+        );
+        assert_eq!(manager.get(rsvp1.id).await.unwrap(), rsvp1);
+
+        let results = manager
+            .confirm_many(vec![rsvp1.id, rsvp2.id], false)
+            .await
+            .unwrap();
+        let rsvp = results[0].as_ref().unwrap();
+        assert_eq!(rsvp.status, ReservationStatus::Confirmed as i32);
+        assert!(results[1].is_err());
+    }
+
+    #[tokio::test]
+    async fn cancel_many_and_get_many_should_work() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let (rsvp1, manager) = make_user_one_reservation(get_pool(&test_postgres).await).await;
+        let (rsvp2, _) = make_reservation(
+            manager.pool.clone(),
+            "user_id_2",
+            "lab-1",
+            "2022-12-26T15:00:00-0700",
+            "2022-12-30T12:00:00-0700",
+            "hello I'm user 2.",
+        )
+        .await;
+
+        let results = manager
+            .cancel_many(vec![rsvp2.id, rsvp1.id], "event moved".into(), true)
+            .await
+            .unwrap();
+        assert!(results.iter().all(|r| {
+            let rsvp = r.as_ref().unwrap();
+            rsvp.status == ReservationStatus::Cancelled as i32
+                && rsvp.cancel_reason == "event moved"
+        }));
+        assert_eq!(results[0].as_ref().unwrap().id, rsvp2.id);
+
+        let results = manager
+            .get_many(vec![rsvp1.id, 10000, rsvp1.id])
+            .await
+            .unwrap();
+        assert_eq!(results[1], Err(abi::Error::NotFound));
+        assert_eq!(results[0], results[2]);
+        assert_eq!(
+            results[0].as_ref().unwrap().status,
+            ReservationStatus::Cancelled as i32
+        );
+    }
+
+    #[tokio::test]
+    async fn reserve_idempotent_should_return_the_first_reservation() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let manager = ReservationManager::new(get_pool(&test_postgres).await);
+        let rsvp = abi::Reservation::new_pending(
+            "user_id_1",
+            "ocean-view-room-713",
+            "2022-12-25T15:00:00-0700".parse().unwrap(),
+            "2022-12-28T12:00:00-0700".parse().unwrap(),
+            "hello.",
+        );
+
+        let first = manager
+            .reserve_idempotent(rsvp.clone(), "retry-key".into())
+            .await
+            .unwrap();
+        let retried = manager
+            .reserve_idempotent(rsvp.clone(), "retry-key".into())
+            .await
+            .unwrap();
+        assert_eq!(first, retried);
+
+        // keys are scoped per user, so another user's key conflicts as usual
+        let other = abi::Reservation {
+            user_id: "user_id_2".into(),
+            ..rsvp.clone()
+        };
+        let err = manager
+            .reserve_idempotent(other, "retry-key".into())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, abi::Error::ConflictReservation(_)));
+
+        let err = manager
+            .reserve_idempotent(rsvp, "k".repeat(65))
+            .await
+            .unwrap_err();
+        assert_eq!(err, abi::Error::InvalidIdempotencyKey("k".repeat(65)));
+    }
+
+    #[tokio::test]
+    async fn expected_version_should_reject_stale_changes() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let (rsvp, manager) = make_user_one_reservation(get_pool(&test_postgres).await).await;
+        assert_eq!(rsvp.version, 1);
+
+        let rsvp = manager
+            .update_note(rsvp.id, "admin 1".into(), Some(1))
+            .await
+            .unwrap();
+        assert_eq!(rsvp.version, 2);
+        let err = manager
+            .update_note(rsvp.id, "admin 2".into(), Some(1))
+            .await
+            .unwrap_err();
+        assert_eq!(err, abi::Error::VersionMismatch(1, 2));
+
+        let err = manager.change_status(rsvp.id, Some(1)).await.unwrap_err();
+        assert_eq!(err, abi::Error::VersionMismatch(1, 2));
+        let rsvp = manager.change_status(rsvp.id, Some(2)).await.unwrap();
+        assert_eq!(rsvp.version, 3);
+
+        let err = manager
+            .cancel(rsvp.id, "".into(), Some(2))
+            .await
+            .unwrap_err();
+        assert_eq!(err, abi::Error::VersionMismatch(2, 3));
+        assert_eq!(manager.get(rsvp.id).await.unwrap(), rsvp);
+
+        let err = manager
+            .update_note(10000, "".into(), Some(1))
+            .await
+            .unwrap_err();
+        assert_eq!(err, abi::Error::NotFound);
+    }
+
+    #[tokio::test]
+    async fn check_should_return_conflicts_without_reserving() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let pool = get_pool(&test_postgres).await;
+        let (rsvp1, manager) = make_user_one_reservation(pool.clone()).await;
+
+        let rsvp2 = abi::Reservation::new_pending(
+            "user_id_2",
+            "ocean-view-room-713",
+            "2022-12-26T15:00:00-0700".parse().unwrap(),
+            "2022-12-30T12:00:00-0700".parse().unwrap(),
+            "hello.",
+        );
+        let conflicts = manager.check(rsvp2.clone()).await.unwrap();
+        assert_eq!(conflicts, vec![rsvp1]);
+
+        let rsvp3 = abi::Reservation {
+            resource_id: "ocean-view-room-714".into(),
+            ..rsvp2.clone()
+        };
+        assert!(manager.check(rsvp3).await.unwrap().is_empty());
+
+        let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM rsvp.reservations")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(count, 1);
+
+        let rsvp4 = abi::Reservation {
+            user_id: "".into(),
+            ..rsvp2
+        };
+        let err = manager.check(rsvp4).await.unwrap_err();
+        assert_eq!(err, abi::Error::InvalidUserId("".into()));
+    }
+
+    #[tokio::test]
+    async fn reserve_conflict_reservation_should_reject() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let pool = get_pool(&test_postgres).await;
+        let manager = ReservationManager::new(pool);
+        let rsvp1 = abi::Reservation::new_pending(
+            "user_id_1",
+            "ocean-view-room-713",
+            "2022-12-25T15:00:00-0700".parse().unwrap(),
+            "2022-12-28T12:00:00-0700".parse().unwrap(),
+            "hello I'm user 1.",
+        );
+        let rsvp2 = abi::Reservation::new_pending(
+            "user_id_2",
+            "ocean-view-room-713",
+            "2022-12-26T15:00:00-0700".parse().unwrap(),
+            "2022-12-30T12:00:00-0700".parse().unwrap(),
+            "hello I'm user 2.",
+        );
+        let rsvp1 = manager.reserve(rsvp1).await.unwrap();
+        let err = manager.reserve(rsvp2).await.unwrap_err();
+
+        let info = ReservationConflictInfo::Parsed(ReservationConflict {
+            new: ReservationWindow {
+                rid: "ocean-view-room-713".to_string(),
+                start: "2022-12-26T15:00:00-0700".parse().unwrap(),
+                end: "2022-12-30T12:00:00-0700".parse().unwrap(),
+            },
+            old: ReservationWindow {
+                rid: "ocean-view-room-713".to_string(),
+                start: "2022-12-25T15:00:00-0700".parse().unwrap(),
+                end: "2022-12-28T12:00:00-0700".parse().unwrap(),
+            },
+            conflicting_id: Some(rsvp1.id),
+        });
+
+        assert_eq!(err, abi::Error::ConflictReservation(info));
+    }
+
+    #[tokio::test]
+    async fn reserve_change_status_should_work() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let pool = get_pool(&test_postgres).await;
+        let (rsvp, manager) = make_user_one_reservation(pool).await;
+        assert!(rsvp.id != 0);
+
+        let rsvp = manager.change_status(rsvp.id, None).await.unwrap();
+        assert_eq!(rsvp.status, abi::ReservationStatus::Confirmed as i32);
+    }
+
+    #[tokio::test]
+    async fn reserve_change_status_not_pending_should_fail() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let pool = get_pool(&test_postgres).await;
+        let (rsvp, manager) = make_user_two_reservation(pool).await;
+        assert!(rsvp.id != 0);
+
+        let rsvp = manager.change_status(rsvp.id, None).await.unwrap();
+        // change status again should fail
+        let err = manager.change_status(rsvp.id, None).await.unwrap_err();
+        assert_eq!(
+            err,
+            abi::Error::IllegalTransition(
+                ReservationStatus::Confirmed,
+                ReservationStatus::Confirmed
+            )
+        );
+
+        let err = manager.change_status(10000, None).await.unwrap_err();
+        assert_eq!(err, abi::Error::NotFound);
+    }
+
+    #[tokio::test]
+    async fn transition_should_follow_allowed_transitions() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let pool = get_pool(&test_postgres).await;
+        let (rsvp, manager) = make_user_one_reservation(pool).await;
+
+        let rsvp = manager
+            .transition(rsvp.id, ReservationStatus::Confirmed, None)
+            .await
+            .unwrap();
+        assert_eq!(rsvp.status(), ReservationStatus::Confirmed);
+        let err = manager
+            .transition(rsvp.id, ReservationStatus::Blocked, None)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err,
+            abi::Error::IllegalTransition(ReservationStatus::Confirmed, ReservationStatus::Blocked)
+        );
+
+        // unconfirm, block and release
+        for status in [
+            ReservationStatus::Pending,
+            ReservationStatus::Blocked,
+            ReservationStatus::Pending,
+        ] {
+            let rsvp = manager.transition(rsvp.id, status, None).await.unwrap();
+            assert_eq!(rsvp.status(), status);
+        }
+
+        let err = manager
+            .transition(rsvp.id, ReservationStatus::Unknown, None)
+            .await
+            .unwrap_err();
+        assert_eq!(err, abi::Error::InvalidStatus(0));
+
+        // a transition based on a stale version is rejected
+        let latest = manager.get(rsvp.id).await.unwrap();
+        let err = manager
+            .transition(rsvp.id, ReservationStatus::Blocked, Some(rsvp.version))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err,
+            abi::Error::VersionMismatch(rsvp.version, latest.version)
+        );
+        let blocked = manager
+            .transition(rsvp.id, ReservationStatus::Blocked, Some(latest.version))
+            .await
+            .unwrap();
+        assert_eq!(blocked.version, latest.version + 1);
+    }
+
+    #[tokio::test]
+    async fn update_note_should_work() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let pool = get_pool(&test_postgres).await;
+        let (rsvp, manager) = make_user_one_reservation(pool).await;
+        let rsvp = manager
+            .update_note(rsvp.id, "hello world".into(), None)
+            .await
+            .unwrap();
+        assert_eq!(rsvp.note, "hello world");
+    }
+
+    #[tokio::test]
+    async fn reschedule_should_keep_id() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let pool = get_pool(&test_postgres).await;
+        let (rsvp, manager) = make_user_one_reservation(pool).await;
+        let start: DateTime<Utc> = "2022-12-25T23:00:00Z".parse().unwrap();
+        let end: DateTime<Utc> = "2022-12-28T20:00:00Z".parse().unwrap();
+        let rescheduled = manager.reschedule(rsvp.id, start, end).await.unwrap();
+        assert_eq!(rescheduled.id, rsvp.id);
+        assert_eq!(rescheduled.start, Some(abi::convert_to_timestamp(&start)));
+        assert_eq!(rescheduled.end, Some(abi::convert_to_timestamp(&end)));
+        assert_eq!(manager.get(rsvp.id).await.unwrap(), rescheduled);
+
+        let err = manager.reschedule(rsvp.id, end, start).await.unwrap_err();
+        assert_eq!(err, abi::Error::InvalidTime);
+
+        let err = manager.reschedule(10000, start, end).await.unwrap_err();
+        assert_eq!(err, abi::Error::NotFound);
+    }
+
+    #[tokio::test]
+    async fn reschedule_cancelled_reservation_should_reject() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let pool = get_pool(&test_postgres).await;
+        let (rsvp, manager) = make_user_one_reservation(pool).await;
+        let cancelled = manager
+            .cancel(rsvp.id, "plans changed".into(), None)
+            .await
+            .unwrap();
+
+        let err = manager
+            .reschedule(
+                rsvp.id,
+                "2022-12-25T23:00:00Z".parse().unwrap(),
+                "2022-12-28T20:00:00Z".parse().unwrap(),
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err, abi::Error::ReservationCancelled(rsvp.id));
+        assert_eq!(manager.get(rsvp.id).await.unwrap(), cancelled);
+    }
+
+    #[tokio::test]
+    async fn reschedule_conflict_reservation_should_reject() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let pool = get_pool(&test_postgres).await;
+        let (rsvp1, manager) = make_user_one_reservation(pool.clone()).await;
+        let (rsvp2, _) = make_reservation(
+            pool,
+            "user_id_2",
+            "ocean-view-room-713",
+            "2022-12-29T15:00:00-0700",
+            "2022-12-30T12:00:00-0700",
+            "hello I'm user 2.",
+        )
+        .await;
+
+        let start = "2022-12-27T22:00:00Z".parse().unwrap();
+        let end = "2022-12-30T19:00:00Z".parse().unwrap();
+        let err = manager.reschedule(rsvp2.id, start, end).await.unwrap_err();
+        let info = ReservationConflictInfo::Parsed(ReservationConflict {
+            new: ReservationWindow {
+                rid: "ocean-view-room-713".to_string(),
+                start,
+                end,
+            },
+            old: ReservationWindow {
+                rid: "ocean-view-room-713".to_string(),
+                start: "2022-12-25T15:00:00-0700".parse().unwrap(),
+                end: "2022-12-28T12:00:00-0700".parse().unwrap(),
+            },
+            conflicting_id: Some(rsvp1.id),
+        });
+        assert_eq!(err, abi::Error::ConflictReservation(info));
+        assert_eq!(manager.get(rsvp2.id).await.unwrap(), rsvp2);
+    }
+
+    #[tokio::test]
+    async fn get_reservation_should_work() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let pool = get_pool(&test_postgres).await;
+        let (new_rsvp, manager) = make_user_one_reservation(pool).await;
+        let get_rsvp = manager.get(new_rsvp.id).await.unwrap();
+        assert_eq!(new_rsvp, get_rsvp);
+    }
+
+    #[tokio::test]
+    async fn expired_holds_should_be_cancelled() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let pool = get_pool(&test_postgres).await;
+        let manager = ReservationManager::new(pool.clone()).with_hold_ttl(900);
+
+        // default ttl for pending reservations, none for confirmed ones
+        let held = manager
+            .reserve(abi::Reservation::new_pending(
+                "user_id_1",
+                "ocean-view-room-713",
+                "2022-12-25T15:00:00-0700".parse().unwrap(),
+                "2022-12-28T12:00:00-0700".parse().unwrap(),
+                "hold me",
+            ))
+            .await
+            .unwrap();
+        assert!(held.expires_at.is_some());
+        assert_eq!(
+            manager.get(held.id).await.unwrap().expires_at,
+            held.expires_at
+        );
+
+        let confirmed = manager
+            .reserve(abi::Reservation {
+                status: ReservationStatus::Confirmed as i32,
+                ..abi::Reservation::new_pending(
+                    "user_id_1",
+                    "ocean-view-room-714",
+                    "2022-12-25T15:00:00-0700".parse().unwrap(),
+                    "2022-12-28T12:00:00-0700".parse().unwrap(),
+                    "confirmed",
+                )
+            })
+            .await
+            .unwrap();
+        assert_eq!(confirmed.expires_at, None);
+
+        // per reservation ttl overrides the default
+        let short = manager
+            .reserve(abi::Reservation {
+                hold_ttl_secs: 1,
+                ..abi::Reservation::new_pending(
+                    "user_id_2",
+                    "ocean-view-room-715",
+                    "2022-12-25T15:00:00-0700".parse().unwrap(),
+                    "2022-12-28T12:00:00-0700".parse().unwrap(),
+                    "short hold",
+                )
+            })
+            .await
+            .unwrap();
+        assert!(short.expires_at.unwrap().seconds < held.expires_at.unwrap().seconds);
+
+        assert!(manager.expire_holds().await.unwrap().is_empty());
+        sqlx::query(
+            "UPDATE rsvp.reservations SET expires_at = now() - interval '1 second' WHERE id = $1",
+        )
+        .bind(short.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        // an expired hold can't be confirmed before the reaper gets to it
+        assert_eq!(
+            manager.change_status(short.id, None).await.unwrap_err(),
+            abi::Error::HoldExpired(short.id)
+        );
+        let expired = manager.expire_holds().await.unwrap();
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].id, short.id);
+        assert_eq!(expired[0].status(), ReservationStatus::Cancelled);
+        assert_eq!(expired[0].cancel_reason, HOLD_EXPIRED_REASON);
+
+        // confirming clears the expiry
+        let held = manager.change_status(held.id, None).await.unwrap();
+        assert_eq!(held.expires_at, None);
+    }
+
+    #[tokio::test]
+    async fn cancel_reservation_should_keep_it_and_free_the_slot() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let pool = get_pool(&test_postgres).await;
+        let (rsvp, manager) = make_user_one_reservation(pool.clone()).await;
+        let cancelled = manager
+            .cancel(rsvp.id, "plans changed".into(), None)
+            .await
+            .unwrap();
+        assert_eq!(cancelled.status(), ReservationStatus::Cancelled);
+        assert_eq!(cancelled.cancel_reason, "plans changed");
+        assert!(cancelled.cancelled_at.is_some());
+        assert_eq!(manager.get(rsvp.id).await.unwrap(), cancelled);
+
+        // the same window could be reserved again
+        let (rsvp2, _) = make_user_one_reservation(pool).await;
+        assert_eq!(manager.check(rsvp2.clone()).await.unwrap(), vec![rsvp2]);
+
+        let err = manager.cancel(rsvp.id, "".into(), None).await.unwrap_err();
+        assert_eq!(
+            err,
+            abi::Error::IllegalTransition(
+                ReservationStatus::Cancelled,
+                ReservationStatus::Cancelled
+            )
+        );
+
+        let filter = ReservationFilterBuilder::default()
+            .status(ReservationStatus::Cancelled as i32)
+            .build()
+            .unwrap();
+        let (_, rsvps) = manager.filter(filter).await.unwrap();
+        assert_eq!(rsvps, vec![cancelled]);
     }
 
     #[tokio::test]
     async fn delete_reservation_should_work() {
         let test_postgres = TestPostgres::new("../migrations").await.unwrap();
-        let pool = test_postgres.get_pool().await;
+        let pool = get_pool(&test_postgres).await;
         let (rsvp, manager) = make_user_one_reservation(pool).await;
         manager.delete(rsvp.id).await.unwrap();
         let err = manager.get(rsvp.id).await.unwrap_err();
@@ -270,7 +2322,7 @@ mod tests {
     #[tokio::test]
     async fn query_reservations_should_work() {
         let test_postgres = TestPostgres::new("../migrations").await.unwrap();
-        let pool = test_postgres.get_pool().await;
+        let pool = get_pool(&test_postgres).await;
         let (rsvp, manager) = make_user_one_reservation(pool).await;
         assert!(rsvp.id != 0);
 
@@ -279,7 +2331,7 @@ mod tests {
             .resource_id("ocean-view-room-713")
             .start("2022-12-25T15:00:00-0700".parse::<Timestamp>().unwrap())
             .end("2022-12-28T12:00:00-0700".parse::<Timestamp>().unwrap())
-            .status(ReservationStatus::Pending)
+            .status(ReservationStatus::Pending as i32)
             .build()
             .unwrap();
 
@@ -293,7 +2345,7 @@ mod tests {
             .resource_id("ocean-view-room-713")
             .start("2023-12-25T15:00:00-0700".parse::<Timestamp>().unwrap())
             .end("2023-12-28T12:00:00-0700".parse::<Timestamp>().unwrap())
-            .status(ReservationStatus::Pending)
+            .status(ReservationStatus::Pending as i32)
             .build()
             .unwrap();
         let mut rx = manager.query(query).await;
@@ -305,30 +2357,84 @@ mod tests {
             .resource_id("ocean-view-room-713")
             .start("2022-12-25T15:00:00-0700".parse::<Timestamp>().unwrap())
             .end("2022-12-28T12:00:00-0700".parse::<Timestamp>().unwrap())
-            .status(ReservationStatus::Confirmed)
+            .status(ReservationStatus::Confirmed as i32)
             .build()
             .unwrap();
         let mut rx = manager.query(query.clone()).await;
         assert_eq!(rx.recv().await, None);
 
         // change state to confirmed, query should get result
-        let rsvp = manager.change_status(rsvp.id).await.unwrap();
+        let rsvp = manager.change_status(rsvp.id, None).await.unwrap();
         let mut rx = manager.query(query).await;
         assert_eq!(rx.recv().await.unwrap(), Ok(rsvp));
         assert_eq!(rx.recv().await, None);
     }
 
+    #[tokio::test]
+    async fn query_and_filter_should_accept_multiple_statuses() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let pool = get_pool(&test_postgres).await;
+        let (pending, manager) = make_user_one_reservation(pool).await;
+        let confirmed = manager
+            .reserve(abi::Reservation::new_pending(
+                "user_id_1",
+                "ocean-view-room-714",
+                "2022-12-25T15:00:00-0700".parse().unwrap(),
+                "2022-12-28T12:00:00-0700".parse().unwrap(),
+                "confirmed one",
+            ))
+            .await
+            .unwrap();
+        let confirmed = manager.change_status(confirmed.id, None).await.unwrap();
+
+        let query = ReservationQueryBuilder::default()
+            .user_id("user_id_1")
+            .status(ReservationStatus::Pending as i32)
+            .status(ReservationStatus::Confirmed as i32)
+            .build()
+            .unwrap();
+        let mut rx = manager.query(query).await;
+        assert_eq!(rx.recv().await.unwrap(), Ok(pending.clone()));
+        assert_eq!(rx.recv().await.unwrap(), Ok(confirmed.clone()));
+        assert_eq!(rx.recv().await, None);
+
+        // no status means all statuses
+        let filter = ReservationFilterBuilder::default()
+            .user_id("user_id_1")
+            .build()
+            .unwrap();
+        let (_, rsvps) = manager.filter(filter).await.unwrap();
+        assert_eq!(rsvps, vec![pending.clone(), confirmed.clone()]);
+
+        // unknown is the same as all statuses
+        let filter = ReservationFilterBuilder::default()
+            .user_id("user_id_1")
+            .status(ReservationStatus::Confirmed as i32)
+            .status(ReservationStatus::Unknown as i32)
+            .build()
+            .unwrap();
+        let (_, rsvps) = manager.filter(filter).await.unwrap();
+        assert_eq!(rsvps, vec![pending, confirmed]);
+
+        let query = abi::ReservationQuery {
+            statuses: vec![10],
+            ..Default::default()
+        };
+        let mut rx = manager.query(query).await;
+        assert_eq!(rx.recv().await.unwrap(), Err(abi::Error::InvalidStatus(10)));
+    }
+
     #[tokio::test]
     async fn filter_reservations_should_work() {
         let test_postgres = TestPostgres::new("../migrations").await.unwrap();
-        let pool = test_postgres.get_pool().await;
+        let pool = get_pool(&test_postgres).await;
         let (rsvp, manager) = make_user_one_reservation(pool).await;
         assert!(rsvp.id != 0);
 
         let filter = ReservationFilterBuilder::default()
             .user_id("user_id_1")
             .resource_id("ocean-view-room-713")
-            .status(ReservationStatus::Pending)
+            .status(ReservationStatus::Pending as i32)
             .build()
             .unwrap();
 
@@ -339,8 +2445,588 @@ mod tests {
         assert_eq!(rsvps[0], rsvp);
     }
 
+    #[tokio::test]
+    async fn query_reservations_should_honor_mode() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let pool = get_pool(&test_postgres).await;
+        let (rsvp, manager) = make_user_one_reservation(pool).await;
+
+        // a window inside the reservation, e.g. "what is booked today"
+        let builder = ReservationQueryBuilder::default()
+            .resource_id("ocean-view-room-713")
+            .start("2022-12-26T00:00:00-0700".parse::<Timestamp>().unwrap())
+            .end("2022-12-27T00:00:00-0700".parse::<Timestamp>().unwrap())
+            .clone();
+
+        let mut rx = manager.query(builder.clone().build().unwrap()).await;
+        assert_eq!(rx.recv().await, None);
+
+        let query = builder
+            .clone()
+            .mode(ReservationQueryMode::Overlapping)
+            .build()
+            .unwrap();
+        let mut rx = manager.query(query).await;
+        assert_eq!(rx.recv().await.unwrap(), Ok(rsvp.clone()));
+        assert_eq!(rx.recv().await, None);
+
+        let query = builder
+            .clone()
+            .mode(ReservationQueryMode::StartingWithin)
+            .build()
+            .unwrap();
+        let mut rx = manager.query(query).await;
+        assert_eq!(rx.recv().await, None);
+
+        let query = builder
+            .clone()
+            .start("2022-12-25T00:00:00-0700".parse::<Timestamp>().unwrap())
+            .mode(ReservationQueryMode::StartingWithin)
+            .build()
+            .unwrap();
+        let mut rx = manager.query(query).await;
+        assert_eq!(rx.recv().await.unwrap(), Ok(rsvp));
+        assert_eq!(rx.recv().await, None);
+    }
+
+    #[tokio::test]
+    async fn query_and_filter_should_work_for_ids_with_quotes() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let pool = get_pool(&test_postgres).await;
+        let (rsvp, manager) = make_reservation(
+            pool,
+            "o'brien",
+            "room' OR '1'='1",
+            "2022-12-25T15:00:00-0700",
+            "2022-12-28T12:00:00-0700",
+            "",
+        )
+        .await;
+
+        let query = ReservationQueryBuilder::default()
+            .user_id("o'brien")
+            .resource_id("room' OR '1'='1")
+            .build()
+            .unwrap();
+        let mut rx = manager.query(query).await;
+        assert_eq!(rx.recv().await.unwrap(), Ok(rsvp.clone()));
+        assert_eq!(rx.recv().await, None);
+
+        let filter = ReservationFilterBuilder::default()
+            .resource_id("room' OR '1'='1")
+            .build()
+            .unwrap();
+        let (_, rsvps) = manager.filter(filter).await.unwrap();
+        assert_eq!(rsvps, vec![rsvp]);
+    }
+
+    #[tokio::test]
+    async fn listen_should_receive_reservation_changes() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let pool = get_pool(&test_postgres).await;
+        let manager = ReservationManager::new(pool.clone());
+        let mut rx = manager.listen(abi::ListenRequest::default()).await;
+
+        let (rsvp, manager) = make_user_one_reservation(pool).await;
+        let confirmed = manager.change_status(rsvp.id, None).await.unwrap();
+        let deleted = manager.delete(rsvp.id).await.unwrap();
+
+        let change = rx.recv().await.unwrap().unwrap();
+        assert_eq!(change.op, ReservationUpdateType::Create as i32);
+        assert_eq!(change.reservation, Some(rsvp));
+
+        let change = rx.recv().await.unwrap().unwrap();
+        assert_eq!(change.op, ReservationUpdateType::Update as i32);
+        assert_eq!(change.reservation, Some(confirmed));
+
+        let change = rx.recv().await.unwrap().unwrap();
+        assert_eq!(change.op, ReservationUpdateType::Delete as i32);
+        assert_eq!(change.reservation, Some(deleted));
+    }
+
+    #[tokio::test]
+    async fn listen_should_resume_from_consumer_cursor() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let pool = get_pool(&test_postgres).await;
+        let manager = ReservationManager::new(pool.clone());
+        let mut rx = manager.listen(ListenRequest::resume("billing")).await;
+
+        let (rsvp, manager) = make_user_one_reservation(pool.clone()).await;
+        let created = rx.recv().await.unwrap().unwrap();
+        assert_eq!(created.op, ReservationUpdateType::Create as i32);
+        manager
+            .ack(AckRequest::new("billing", created.change_id))
+            .await
+            .unwrap();
+        drop(rx);
+
+        // changes made while the consumer is away should be delivered on reconnect
+        let confirmed = manager.change_status(rsvp.id, None).await.unwrap();
+        let mut rx = manager.listen(ListenRequest::resume("billing")).await;
+        let change = rx.recv().await.unwrap().unwrap();
+        assert_eq!(change.op, ReservationUpdateType::Update as i32);
+        assert_eq!(change.reservation, Some(confirmed.clone()));
+
+        // a change received but not acknowledged is sent again
+        drop(rx);
+        let mut rx = manager.listen(ListenRequest::resume("billing")).await;
+        assert_eq!(rx.recv().await.unwrap().unwrap(), change);
+        let err = manager
+            .ack(AckRequest::new("billing", change.change_id + 100))
+            .await
+            .unwrap_err();
+        assert_eq!(err, abi::Error::InvalidCursor(change.change_id + 100));
+
+        // an explicit last change id replays everything after it
+        let mut rx = manager
+            .listen(ListenRequest {
+                last_change_id: Some(0),
+                ..Default::default()
+            })
+            .await;
+        let change = rx.recv().await.unwrap().unwrap();
+        assert_eq!(change, created);
+        let change = rx.recv().await.unwrap().unwrap();
+        assert_eq!(change.reservation, Some(confirmed));
+    }
+
+    #[tokio::test]
+    async fn listen_should_not_skip_changes_committed_out_of_order() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let pool = get_pool(&test_postgres).await;
+        let manager = ReservationManager::new(pool.clone());
+        let mut rx = manager.listen(abi::ListenRequest::default()).await;
+
+        // the first change id is taken by a transaction that commits after the second one
+        let mut slow = pool.begin().await.unwrap();
+        let mut first = abi::Reservation::new_pending(
+            "alice",
+            "ocean-view-room-713",
+            "2022-12-25T15:00:00-0700".parse().unwrap(),
+            "2022-12-28T12:00:00-0700".parse().unwrap(),
+            "",
+        );
+        let rid = first.resource_id.clone();
+        let resources = lock_resources(&mut slow, std::slice::from_ref(&rid))
+            .await
+            .unwrap();
+        insert_reservation(&mut slow, &mut first, resources.get(&rid), 0, None, None)
+            .await
+            .unwrap();
+        let (second, _) = make_reservation(
+            pool.clone(),
+            "bob",
+            "ocean-view-room-714",
+            "2022-12-25T15:00:00-0700",
+            "2022-12-28T12:00:00-0700",
+            "",
+        )
+        .await;
+
+        // the second change is held back until the first one is committed
+        let held = tokio::time::timeout(Duration::from_millis(300), rx.recv()).await;
+        assert!(held.is_err());
+        slow.commit().await.unwrap();
+        let change = rx.recv().await.unwrap().unwrap();
+        assert_eq!(change.reservation.unwrap().id, first.id);
+        let change = rx.recv().await.unwrap().unwrap();
+        assert_eq!(change.reservation, Some(second));
+    }
+
+    #[tokio::test]
+    async fn listen_should_filter_changes() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let pool = get_pool(&test_postgres).await;
+        let manager = ReservationManager::new(pool.clone());
+        let request = ListenRequest {
+            consumer_id: Some("kiosk".into()),
+            resource_id_prefix: "floor-3-".into(),
+            ops: vec![ReservationUpdateType::Update as i32],
+            ..Default::default()
+        };
+        let mut rx = manager.listen(request.clone()).await;
+
+        let (rsvp1, _) = make_reservation(
+            pool.clone(),
+            "alice",
+            "floor-3-room-301",
+            "2022-12-25T15:00:00-0700",
+            "2022-12-28T12:00:00-0700",
+            "",
+        )
+        .await;
+        let (rsvp2, manager) = make_reservation(
+            pool.clone(),
+            "bob",
+            "floor-4-room-401",
+            "2022-12-25T15:00:00-0700",
+            "2022-12-28T12:00:00-0700",
+            "",
+        )
+        .await;
+        manager.change_status(rsvp2.id, None).await.unwrap();
+        let confirmed = manager.change_status(rsvp1.id, None).await.unwrap();
+
+        // only the update of the floor 3 room should be sent
+        let change = rx.recv().await.unwrap().unwrap();
+        assert_eq!(change.op, ReservationUpdateType::Update as i32);
+        assert_eq!(change.reservation, Some(confirmed));
+
+        // skipped changes should not be replayed to the consumer
+        manager
+            .ack(AckRequest::new("kiosk", change.change_id))
+            .await
+            .unwrap();
+        drop(rx);
+        manager.delete(rsvp1.id).await.unwrap();
+        let mut rx = manager.listen(request).await;
+        let (rsvp3, manager) = make_reservation(
+            pool.clone(),
+            "carol",
+            "floor-3-room-302",
+            "2022-12-25T15:00:00-0700",
+            "2022-12-28T12:00:00-0700",
+            "",
+        )
+        .await;
+        let confirmed = manager.change_status(rsvp3.id, None).await.unwrap();
+        let change = rx.recv().await.unwrap().unwrap();
+        assert_eq!(change.reservation, Some(confirmed));
+    }
+
+    #[tokio::test]
+    async fn history_should_return_reservation_changes() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let pool = get_pool(&test_postgres).await;
+        let (rsvp, manager) = make_user_one_reservation(pool).await;
+        let confirmed = manager.change_status(rsvp.id, None).await.unwrap();
+        let deleted = manager.delete(rsvp.id).await.unwrap();
+
+        let request = HistoryRequestBuilder::default()
+            .id(rsvp.id)
+            .build()
+            .unwrap();
+        let (pager, changes) = manager.history(request).await.unwrap();
+        assert_eq!(pager.prev, None);
+        assert_eq!(pager.next, None);
+        assert_eq!(changes.len(), 3);
+
+        assert_eq!(changes[0].op, ReservationUpdateType::Create as i32);
+        assert_eq!(changes[0].old, None);
+        assert_eq!(changes[0].new, Some(rsvp.clone()));
+
+        assert_eq!(changes[1].op, ReservationUpdateType::Update as i32);
+        assert_eq!(changes[1].old, Some(rsvp));
+        assert_eq!(changes[1].new, Some(confirmed.clone()));
+
+        assert_eq!(changes[2].op, ReservationUpdateType::Delete as i32);
+        assert_eq!(changes[2].old, Some(deleted));
+        assert_eq!(changes[2].new, None);
+
+        // newest change first
+        let request = HistoryRequestBuilder::default()
+            .id(confirmed.id)
+            .desc(true)
+            .build()
+            .unwrap();
+        let (_, desc_changes) = manager.history(request).await.unwrap();
+        assert_eq!(desc_changes, changes.into_iter().rev().collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn reschedule_should_be_recorded_as_change() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let pool = get_pool(&test_postgres).await;
+        let manager = ReservationManager::new(pool.clone());
+        let mut rx = manager.listen(abi::ListenRequest::default()).await;
+
+        let (rsvp, manager) = make_user_one_reservation(pool).await;
+        let rescheduled = manager
+            .reschedule(
+                rsvp.id,
+                "2022-12-26T22:00:00Z".parse().unwrap(),
+                "2022-12-29T19:00:00Z".parse().unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let change = rx.recv().await.unwrap().unwrap();
+        assert_eq!(change.op, ReservationUpdateType::Create as i32);
+        let change = rx.recv().await.unwrap().unwrap();
+        assert_eq!(change.op, ReservationUpdateType::Update as i32);
+        assert_eq!(change.reservation, Some(rescheduled.clone()));
+
+        let request = HistoryRequestBuilder::default()
+            .id(rsvp.id)
+            .build()
+            .unwrap();
+        let (_, changes) = manager.history(request).await.unwrap();
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[1].op, ReservationUpdateType::Update as i32);
+        assert_eq!(changes[1].old, Some(rsvp));
+        assert_eq!(changes[1].new, Some(rescheduled));
+    }
+
+    #[tokio::test]
+    async fn history_should_be_paged() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let pool = get_pool(&test_postgres).await;
+        let (rsvp, manager) = make_user_one_reservation(pool.clone()).await;
+        // status changes are recorded, so flip the status back and forth to make 15 changes
+        for _ in 0..7 {
+            manager.change_status(rsvp.id, None).await.unwrap();
+            sqlx::query("UPDATE rsvp.reservations SET status = 'pending' WHERE id = $1")
+                .bind(rsvp.id)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        let request = HistoryRequestBuilder::default()
+            .id(rsvp.id)
+            .build()
+            .unwrap();
+        let (pager, changes) = manager.history(request.clone()).await.unwrap();
+        assert_eq!(changes.len(), 10);
+        assert_eq!(pager.prev, None);
+        assert_eq!(pager.next, Some(changes[9].id));
+
+        let request = request.next_page(&pager).unwrap();
+        let (pager, changes) = manager.history(request).await.unwrap();
+        assert_eq!(changes.len(), 5);
+        assert_eq!(pager.next, None);
+    }
+
+    #[tokio::test]
+    async fn listen_with_invalid_consumer_id_should_fail() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let pool = get_pool(&test_postgres).await;
+        let manager = ReservationManager::new(pool);
+        let mut rx = manager.listen(ListenRequest::resume("")).await;
+        let err = rx.recv().await.unwrap().unwrap_err();
+        assert_eq!(err, abi::Error::InvalidConsumerId("".into()));
+        assert!(rx.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn listen_with_unknown_cursor_should_fail() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let pool = get_pool(&test_postgres).await;
+        let (_, manager) = make_user_one_reservation(pool).await;
+        let mut rx = manager
+            .listen(ListenRequest {
+                last_change_id: Some(1000),
+                ..Default::default()
+            })
+            .await;
+        let err = rx.recv().await.unwrap().unwrap_err();
+        assert_eq!(err, abi::Error::InvalidCursor(1000));
+        assert!(rx.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn availability_should_return_free_slots() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let pool = get_pool(&test_postgres).await;
+        // user 1: 12-25 22:00 ~ 12-28 19:00 UTC, user 2 on another room: 12-26 22:00 ~ 12-30 19:00 UTC
+        let (_, manager) = make_user_one_reservation(pool.clone()).await;
+        make_reservation(
+            pool,
+            "user_id_2",
+            "ocean-view-room-714",
+            "2022-12-26T15:00:00-0700",
+            "2022-12-30T12:00:00-0700",
+            "hello I'm user 2.",
+        )
+        .await;
+
+        let request = AvailabilityRequestBuilder::default()
+            .resource_ids(vec![
+                "ocean-view-room-713".to_string(),
+                "ocean-view-room-714".to_string(),
+                "ocean-view-room-715".to_string(),
+            ])
+            .start("2022-12-25T00:00:00Z".parse::<Timestamp>().unwrap())
+            .end("2022-12-29T00:00:00Z".parse::<Timestamp>().unwrap())
+            .build()
+            .unwrap();
+        let slots = manager.availability(request.clone()).await.unwrap();
+        let utc = |s: &str| s.parse().unwrap();
+        assert_eq!(
+            slots,
+            vec![
+                FreeSlot::new(
+                    "ocean-view-room-713",
+                    utc("2022-12-25T00:00:00Z"),
+                    utc("2022-12-25T22:00:00Z")
+                ),
+                FreeSlot::new(
+                    "ocean-view-room-713",
+                    utc("2022-12-28T19:00:00Z"),
+                    utc("2022-12-29T00:00:00Z")
+                ),
+                FreeSlot::new(
+                    "ocean-view-room-714",
+                    utc("2022-12-25T00:00:00Z"),
+                    utc("2022-12-26T22:00:00Z")
+                ),
+                FreeSlot::new(
+                    "ocean-view-room-715",
+                    utc("2022-12-25T00:00:00Z"),
+                    utc("2022-12-29T00:00:00Z")
+                ),
+            ]
+        );
+
+        // only slots of at least 12 hours
+        let request = abi::AvailabilityRequest {
+            min_duration_secs: 12 * 3600,
+            ..request
+        };
+        let slots = manager.availability(request).await.unwrap();
+        assert_eq!(slots.len(), 3);
+        assert!(slots
+            .iter()
+            .all(|s| s.start != Some("2022-12-28T19:00:00Z".parse::<Timestamp>().unwrap())));
+    }
+
+    #[tokio::test]
+    async fn pooled_resource_should_allow_overlaps_up_to_capacity() {
+        let test_postgres = TestPostgres::new("../migrations").await.unwrap();
+        let pool = get_pool(&test_postgres).await;
+        let manager = ReservationManager::new(pool);
+        let desks = manager
+            .create_resource(abi::Resource::new("desk-pool-1", "Desks", "desk").with_capacity(2))
+            .await
+            .unwrap();
+        let desk = |start: &str, end: &str| {
+            abi::Reservation::new_pending(
+                "user_id_1",
+                "desk-pool-1",
+                start.parse().unwrap(),
+                end.parse().unwrap(),
+                "desk",
+            )
+        };
+        let utc = |s: &str| s.parse().unwrap();
+        let window = |start: &str, end: &str| ReservationWindow {
+            rid: "desk-pool-1".into(),
+            start: utc(start),
+            end: utc(end),
+        };
+
+        let rsvp1 = manager
+            .reserve(desk("2022-12-20T09:00:00Z", "2022-12-20T12:00:00Z"))
+            .await
+            .unwrap();
+        let rsvp2 = manager
+            .reserve(desk("2022-12-20T10:00:00Z", "2022-12-20T14:00:00Z"))
+            .await
+            .unwrap();
+
+        // both desks are taken from 11:00 to 12:00
+        let rsvp3 = desk("2022-12-20T11:00:00Z", "2022-12-20T13:00:00Z");
+        let conflicts = manager.check(rsvp3.clone()).await.unwrap();
+        assert_eq!(conflicts, vec![rsvp1, rsvp2.clone()]);
+        let err = manager.reserve(rsvp3.clone()).await.unwrap_err();
+        assert_eq!(
+            err,
+            abi::Error::ConflictReservation(ReservationConflictInfo::Full(abi::ResourceFull {
+                new: window("2022-12-20T11:00:00Z", "2022-12-20T13:00:00Z"),
+                full: window("2022-12-20T11:00:00Z", "2022-12-20T12:00:00Z"),
+                capacity: 2,
+            }))
+        );
+
+        // one desk is left once the second reservation moves after the first one
+        manager
+            .reschedule(
+                rsvp2.id,
+                utc("2022-12-20T12:00:00Z"),
+                utc("2022-12-20T14:00:00Z"),
+            )
+            .await
+            .unwrap();
+        assert!(manager.check(rsvp3.clone()).await.unwrap().is_empty());
+        manager.reserve(rsvp3).await.unwrap();
+
+        let request = AvailabilityRequestBuilder::default()
+            .resource_ids(vec!["desk-pool-1".to_string()])
+            .start("2022-12-20T08:00:00Z".parse::<Timestamp>().unwrap())
+            .end("2022-12-20T16:00:00Z".parse::<Timestamp>().unwrap())
+            .build()
+            .unwrap();
+        let slots = manager.availability(request).await.unwrap();
+        assert_eq!(
+            slots,
+            vec![
+                FreeSlot::new(
+                    "desk-pool-1",
+                    utc("2022-12-20T08:00:00Z"),
+                    utc("2022-12-20T11:00:00Z")
+                ),
+                FreeSlot::new(
+                    "desk-pool-1",
+                    utc("2022-12-20T13:00:00Z"),
+                    utc("2022-12-20T16:00:00Z")
+                ),
+            ]
+        );
+
+        // a single desk can't hold the overlapping reservations
+        let err = manager
+            .update_resource(desks.with_capacity(1))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, abi::Error::ConflictReservation(_)));
+    }
+
     //==========================================================================
     // private none test function
+    /// resources reserved by the tests
+    const RESOURCES: &[&str] = &[
+        "ocean-view-room-713",
+        "ocean-view-room-714",
+        "ocean-view-room-715",
+        "floor-3-room-301",
+        "floor-3-room-302",
+        "floor-4-room-401",
+        "lab-1",
+        "room-1",
+        "projector-1",
+        "bridge-1",
+        "room' OR '1'='1",
+    ];
+
+    /// pool of the test database with RESOURCES in the catalog
+    async fn get_pool(test_postgres: &TestPostgres) -> PgPool {
+        let pool = test_postgres.get_pool().await;
+        sqlx::query(
+            "INSERT INTO rsvp.resources (id, name) SELECT id, id FROM unnest($1::text[]) id",
+        )
+        .bind(RESOURCES)
+        .execute(&pool)
+        .await
+        .unwrap();
+        pool
+    }
+
+    fn make_recurring_request(mode: RecurrenceMode) -> abi::ReserveRecurringRequest {
+        abi::ReserveRecurringRequest {
+            reservation: Some(abi::Reservation::new_pending(
+                "user_id_1",
+                "lab-1",
+                "2022-12-05T09:00:00-0800".parse().unwrap(),
+                "2022-12-05T11:00:00-0800".parse().unwrap(),
+                "daily lab slot",
+            )),
+            rrule: "FREQ=DAILY;COUNT=3".into(),
+            exdates: vec![],
+            mode: mode as i32,
+            timezone: "America/Los_Angeles".into(),
+        }
+    }
+
     async fn make_user_one_reservation(pool: PgPool) -> (Reservation, ReservationManager) {
         make_reservation(
             pool,
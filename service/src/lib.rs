@@ -1,8 +1,10 @@
 mod service;
 
-use std::pin::Pin;
+use std::{pin::Pin, time::Duration};
 
-use abi::{reservation_service_server::ReservationServiceServer, Config, Reservation};
+use abi::{
+    reservation_service_server::ReservationServiceServer, Config, ListenResponse, Reservation,
+};
 use futures::Stream;
 use reservation::ReservationManager;
 use tokio::sync::mpsc;
@@ -17,10 +19,14 @@ pub struct TonicReceiverStream<T> {
 }
 
 type ReservationStream = Pin<Box<dyn Stream<Item = Result<Reservation, Status>> + Send>>;
+type ListenStream = Pin<Box<dyn Stream<Item = Result<ListenResponse, Status>> + Send>>;
 
 pub async fn start_server(config: &Config) -> Result<(), anyhow::Error> {
     let addr = format!("{}:{}", config.server.host, config.server.port).parse()?;
     let svc = RsvpService::from_config(config).await?;
+    // expired pending reservations are cancelled in the background
+    let interval = Duration::from_secs(config.hold.reap_interval_secs.max(1));
+    tokio::spawn(svc.manager.clone().reap_holds(interval));
     let svc = ReservationServiceServer::new(svc);
     println!("Listening on {}", addr);
     Server::builder().add_service(svc).serve(addr).await?;
@@ -1,21 +1,35 @@
 use std::task::Poll;
 
 use abi::{
-    reservation_service_server::ReservationService, CancelRequest, CancelResponse, Config,
-    ConfirmRequest, ConfirmResponse, FilterRequest, FilterResponse, GetRequest, GetResponse,
-    ListenRequest, QueryRequest, ReserveRequest, ReserveResponse, UpdateRequest, UpdateResponse,
+    reservation_service_server::ReservationService, AckRequest, AckResponse, AddBlackoutRequest,
+    AddBlackoutResponse, AvailabilityRequest, AvailabilityResponse, CancelManyRequest,
+    CancelManyResponse, CancelRequest, CancelResponse, CancelSeriesRequest, CancelSeriesResponse,
+    CheckRequest, CheckResponse, Config, ConfirmManyRequest, ConfirmManyResponse, ConfirmRequest,
+    ConfirmResponse, CreateResourceRequest, CreateResourceResponse, DeleteResourceRequest,
+    DeleteResourceResponse, FilterRequest, FilterResponse, GetManyRequest, GetManyResponse,
+    GetRequest, GetResourceRequest, GetResourceResponse, GetResponse, HistoryRequest,
+    HistoryResponse, ListBlackoutsRequest, ListBlackoutsResponse, ListResourcesRequest,
+    ListResourcesResponse, ListenRequest, QueryRequest, RemoveBlackoutRequest,
+    RemoveBlackoutResponse, RescheduleRequest, RescheduleResponse, ReservationResult,
+    ReserveAnyRequest, ReserveAnyResponse, ReserveManyRequest, ReserveManyResponse,
+    ReserveRecurringRequest, ReserveRecurringResponse, ReserveRequest, ReserveResponse,
+    ShiftSeriesRequest, ShiftSeriesResponse, TransitionRequest, TransitionResponse, UpdateRequest,
+    UpdateResourceRequest, UpdateResourceResponse, UpdateResponse, UpdateSeriesRequest,
+    UpdateSeriesResponse,
 };
 use futures::Stream;
-use reservation::{ReservationManager, Rsvp};
+use reservation::{BatchResult, ReservationManager, ResourceCatalog, Rsvp};
 use tokio::sync::mpsc;
 use tonic::{Request, Response, Status};
 
-use crate::{ReservationStream, RsvpService, TonicReceiverStream};
+use crate::{ListenStream, ReservationStream, RsvpService, TonicReceiverStream};
 
 impl RsvpService {
     pub async fn from_config(config: &Config) -> Result<Self, anyhow::Error> {
         Ok(Self {
-            manager: ReservationManager::from_config(&config.db).await?,
+            manager: ReservationManager::from_config(&config.db)
+                .await?
+                .with_hold_ttl(config.hold.ttl_secs),
         })
     }
 }
@@ -31,44 +45,230 @@ impl ReservationService for RsvpService {
         if request.reservation.is_none() {
             return Err(Status::invalid_argument("missing reservation"));
         }
-        let reservation = self.manager.reserve(request.reservation.unwrap()).await?;
+        let reservation = request.reservation.unwrap();
+        let reservation = if request.group_id.is_empty() {
+            self.manager
+                .reserve_idempotent(reservation, request.idempotency_key)
+                .await?
+        } else {
+            self.manager
+                .reserve_in_group(reservation, request.group_id, request.idempotency_key)
+                .await?
+        };
         Ok(Response::new(ReserveResponse {
             reservation: Some(reservation),
         }))
     }
-    /// confirm a pending reservation, if reservation is not pending, do nothing
+    /// reserve the first free of the candidate resources
+    async fn reserve_any(
+        &self,
+        request: Request<ReserveAnyRequest>,
+    ) -> Result<Response<ReserveAnyResponse>, Status> {
+        let request = request.into_inner();
+        let Some(reservation) = request.reservation else {
+            return Err(Status::invalid_argument("missing reservation"));
+        };
+        let reservation = self
+            .manager
+            .reserve_any(reservation, request.resource_ids, request.idempotency_key)
+            .await?;
+        Ok(Response::new(ReserveAnyResponse {
+            reservation: Some(reservation),
+        }))
+    }
+    /// make several reservations in one transaction, fails with every conflict if any conflicts
+    async fn reserve_many(
+        &self,
+        request: Request<ReserveManyRequest>,
+    ) -> Result<Response<ReserveManyResponse>, Status> {
+        let request = request.into_inner();
+        let reservations = self.manager.reserve_many(request.reservations).await?;
+        Ok(Response::new(ReserveManyResponse { reservations }))
+    }
+    /// make a recurring reservation, occurrences are expanded from the RRULE
+    async fn reserve_recurring(
+        &self,
+        request: Request<ReserveRecurringRequest>,
+    ) -> Result<Response<ReserveRecurringResponse>, Status> {
+        let request = request.into_inner();
+        let (series_id, occurrences) = self.manager.reserve_recurring(request).await?;
+        Ok(Response::new(ReserveRecurringResponse {
+            series_id,
+            occurrences,
+        }))
+    }
+    /// check a reservation like reserve does and return the conflicting reservations, nothing is inserted
+    async fn check(
+        &self,
+        request: Request<CheckRequest>,
+    ) -> Result<Response<CheckResponse>, Status> {
+        let request = request.into_inner();
+        let Some(reservation) = request.reservation else {
+            return Err(Status::invalid_argument("missing reservation"));
+        };
+        let conflicts = self.manager.check(reservation).await?;
+        Ok(Response::new(CheckResponse { conflicts }))
+    }
+    /// confirm a pending reservation, fails if reservation is not pending
     async fn confirm(
         &self,
         request: Request<ConfirmRequest>,
     ) -> Result<Response<ConfirmResponse>, Status> {
         let request = request.into_inner();
-        let reservation = self.manager.change_status(request.id).await?;
+        let reservation = self
+            .manager
+            .change_status(request.id, expected_version(request.expected_version))
+            .await?;
         Ok(Response::new(ConfirmResponse {
             reservation: Some(reservation),
         }))
     }
+    /// confirm several pending reservations, every id gets its own result
+    async fn confirm_many(
+        &self,
+        request: Request<ConfirmManyRequest>,
+    ) -> Result<Response<ConfirmManyResponse>, Status> {
+        let request = request.into_inner();
+        let results = self
+            .manager
+            .confirm_many(request.ids.clone(), request.transactional)
+            .await?;
+        Ok(Response::new(ConfirmManyResponse {
+            results: to_results(request.ids, results),
+        }))
+    }
+    /// move a reservation to another status following the allowed transitions
+    async fn transition(
+        &self,
+        request: Request<TransitionRequest>,
+    ) -> Result<Response<TransitionResponse>, Status> {
+        let request = request.into_inner();
+        let status = abi::ReservationStatus::from_i32(request.status)
+            .ok_or(abi::Error::InvalidStatus(request.status))?;
+        let reservation = self
+            .manager
+            .transition(
+                request.id,
+                status,
+                expected_version(request.expected_version),
+            )
+            .await?;
+        Ok(Response::new(TransitionResponse {
+            reservation: Some(reservation),
+        }))
+    }
     /// update the reservation note
     async fn update(
         &self,
         request: Request<UpdateRequest>,
     ) -> Result<Response<UpdateResponse>, Status> {
         let request = request.into_inner();
-        let reservation = self.manager.update_note(request.id, request.note).await?;
+        let reservation = self
+            .manager
+            .update_note(
+                request.id,
+                request.note,
+                expected_version(request.expected_version),
+            )
+            .await?;
         Ok(Response::new(UpdateResponse {
             reservation: Some(reservation),
         }))
     }
-    /// cancel a reservation
+    /// move the reservation to another time window, fails if the new window conflicts
+    async fn reschedule(
+        &self,
+        request: Request<RescheduleRequest>,
+    ) -> Result<Response<RescheduleResponse>, Status> {
+        let request = request.into_inner();
+        let (Some(start), Some(end)) = (request.start, request.end) else {
+            return Err(Status::invalid_argument("missing start or end time"));
+        };
+        let reservation = self
+            .manager
+            .reschedule(
+                request.id,
+                abi::convert_to_utc_time(&start),
+                abi::convert_to_utc_time(&end),
+            )
+            .await?;
+        Ok(Response::new(RescheduleResponse {
+            reservation: Some(reservation),
+        }))
+    }
+    /// update the note of the selected occurrences of a recurring series
+    async fn update_series(
+        &self,
+        request: Request<UpdateSeriesRequest>,
+    ) -> Result<Response<UpdateSeriesResponse>, Status> {
+        let request = request.into_inner();
+        let Some(series) = request.series else {
+            return Err(Status::invalid_argument("missing series selector"));
+        };
+        let reservations = self
+            .manager
+            .update_series_note(series, request.note)
+            .await?;
+        Ok(Response::new(UpdateSeriesResponse { reservations }))
+    }
+    /// cancel the selected occurrences of a recurring series
+    async fn cancel_series(
+        &self,
+        request: Request<CancelSeriesRequest>,
+    ) -> Result<Response<CancelSeriesResponse>, Status> {
+        let request = request.into_inner();
+        let Some(series) = request.series else {
+            return Err(Status::invalid_argument("missing series selector"));
+        };
+        let reservations = self.manager.cancel_series(series, request.reason).await?;
+        Ok(Response::new(CancelSeriesResponse { reservations }))
+    }
+    /// move the selected occurrences of a recurring series by a fixed offset
+    async fn shift_series(
+        &self,
+        request: Request<ShiftSeriesRequest>,
+    ) -> Result<Response<ShiftSeriesResponse>, Status> {
+        let request = request.into_inner();
+        let Some(series) = request.series else {
+            return Err(Status::invalid_argument("missing series selector"));
+        };
+        let offset = chrono::Duration::try_seconds(request.offset_secs)
+            .ok_or(abi::Error::InvalidDuration(request.offset_secs))?;
+        let reservations = self.manager.shift_series(series, offset).await?;
+        Ok(Response::new(ShiftSeriesResponse { reservations }))
+    }
+    /// cancel a reservation, it is kept with cancelled status and no longer blocks its time window
     async fn cancel(
         &self,
         request: Request<CancelRequest>,
     ) -> Result<Response<CancelResponse>, Status> {
         let request = request.into_inner();
-        let reservation = self.manager.delete(request.id).await?;
+        let reservation = self
+            .manager
+            .cancel(
+                request.id,
+                request.reason,
+                expected_version(request.expected_version),
+            )
+            .await?;
         Ok(Response::new(CancelResponse {
             reservation: Some(reservation),
         }))
     }
+    /// cancel several reservations, every id gets its own result
+    async fn cancel_many(
+        &self,
+        request: Request<CancelManyRequest>,
+    ) -> Result<Response<CancelManyResponse>, Status> {
+        let request = request.into_inner();
+        let results = self
+            .manager
+            .cancel_many(request.ids.clone(), request.reason, request.transactional)
+            .await?;
+        Ok(Response::new(CancelManyResponse {
+            results: to_results(request.ids, results),
+        }))
+    }
     /// get a reservation by id
     async fn get(&self, request: Request<GetRequest>) -> Result<Response<GetResponse>, Status> {
         let request = request.into_inner();
@@ -77,6 +277,17 @@ impl ReservationService for RsvpService {
             reservation: Some(reservation),
         }))
     }
+    /// get several reservations by id, every id gets its own result
+    async fn get_many(
+        &self,
+        request: Request<GetManyRequest>,
+    ) -> Result<Response<GetManyResponse>, Status> {
+        let request = request.into_inner();
+        let results = self.manager.get_many(request.ids.clone()).await?;
+        Ok(Response::new(GetManyResponse {
+            results: to_results(request.ids, results),
+        }))
+    }
     ///Server streaming response type for the query method.
     type queryStream = ReservationStream;
 
@@ -109,17 +320,153 @@ impl ReservationService for RsvpService {
         }))
     }
     ///Server streaming response type for the listen method.
-    type listenStream = ReservationStream;
+    type listenStream = ListenStream;
 
     /// another system could monitor newly added/confirmed/cancelled reservations
     async fn listen(
         &self,
-        _request: Request<ListenRequest>,
+        request: Request<ListenRequest>,
     ) -> Result<Response<Self::listenStream>, Status> {
-        todo!()
+        let request = request.into_inner();
+        let changes = self.manager.listen(request).await;
+        let stream = TonicReceiverStream::new(changes);
+        Ok(Response::new(Box::pin(stream)))
+    }
+    /// acknowledge the changes a consumer has handled
+    async fn ack(&self, request: Request<AckRequest>) -> Result<Response<AckResponse>, Status> {
+        let request = request.into_inner();
+        self.manager.ack(request).await?;
+        Ok(Response::new(AckResponse {}))
+    }
+    /// get the change history of a reservation, including deleted ones
+    async fn history(
+        &self,
+        request: Request<HistoryRequest>,
+    ) -> Result<Response<HistoryResponse>, Status> {
+        let request = request.into_inner();
+        let (pager, changes) = self.manager.history(request).await?;
+        Ok(Response::new(HistoryResponse {
+            changes,
+            pager: Some(pager),
+        }))
+    }
+    /// find free slots of resources within a time window
+    async fn availability(
+        &self,
+        request: Request<AvailabilityRequest>,
+    ) -> Result<Response<AvailabilityResponse>, Status> {
+        let request = request.into_inner();
+        let slots = self.manager.availability(request).await?;
+        Ok(Response::new(AvailabilityResponse { slots }))
+    }
+    /// add a resource to the catalog
+    async fn create_resource(
+        &self,
+        request: Request<CreateResourceRequest>,
+    ) -> Result<Response<CreateResourceResponse>, Status> {
+        let request = request.into_inner();
+        let Some(resource) = request.resource else {
+            return Err(Status::invalid_argument("missing resource"));
+        };
+        let resource = self.manager.create_resource(resource).await?;
+        Ok(Response::new(CreateResourceResponse {
+            resource: Some(resource),
+        }))
+    }
+    /// get a resource by id
+    async fn get_resource(
+        &self,
+        request: Request<GetResourceRequest>,
+    ) -> Result<Response<GetResourceResponse>, Status> {
+        let request = request.into_inner();
+        let resource = self.manager.get_resource(request.id).await?;
+        Ok(Response::new(GetResourceResponse {
+            resource: Some(resource),
+        }))
+    }
+    /// update a resource, deactivate it to stop new reservations on it
+    async fn update_resource(
+        &self,
+        request: Request<UpdateResourceRequest>,
+    ) -> Result<Response<UpdateResourceResponse>, Status> {
+        let request = request.into_inner();
+        let Some(resource) = request.resource else {
+            return Err(Status::invalid_argument("missing resource"));
+        };
+        let resource = self.manager.update_resource(resource).await?;
+        Ok(Response::new(UpdateResourceResponse {
+            resource: Some(resource),
+        }))
+    }
+    /// delete a resource from the catalog
+    async fn delete_resource(
+        &self,
+        request: Request<DeleteResourceRequest>,
+    ) -> Result<Response<DeleteResourceResponse>, Status> {
+        let request = request.into_inner();
+        let resource = self.manager.delete_resource(request.id).await?;
+        Ok(Response::new(DeleteResourceResponse {
+            resource: Some(resource),
+        }))
+    }
+    /// list the resources in the catalog
+    async fn list_resources(
+        &self,
+        request: Request<ListResourcesRequest>,
+    ) -> Result<Response<ListResourcesResponse>, Status> {
+        let request = request.into_inner();
+        let resources = self.manager.list_resources(request).await?;
+        Ok(Response::new(ListResourcesResponse { resources }))
+    }
+    /// block a resource for a period
+    async fn add_blackout(
+        &self,
+        request: Request<AddBlackoutRequest>,
+    ) -> Result<Response<AddBlackoutResponse>, Status> {
+        let request = request.into_inner();
+        let Some(blackout) = request.blackout else {
+            return Err(Status::invalid_argument("missing blackout"));
+        };
+        let blackout = self.manager.add_blackout(blackout).await?;
+        Ok(Response::new(AddBlackoutResponse {
+            blackout: Some(blackout),
+        }))
+    }
+    /// remove a blackout by id
+    async fn remove_blackout(
+        &self,
+        request: Request<RemoveBlackoutRequest>,
+    ) -> Result<Response<RemoveBlackoutResponse>, Status> {
+        let request = request.into_inner();
+        let blackout = self.manager.remove_blackout(request.id).await?;
+        Ok(Response::new(RemoveBlackoutResponse {
+            blackout: Some(blackout),
+        }))
+    }
+    /// list the blackouts of a resource
+    async fn list_blackouts(
+        &self,
+        request: Request<ListBlackoutsRequest>,
+    ) -> Result<Response<ListBlackoutsResponse>, Status> {
+        let request = request.into_inner();
+        let blackouts = self.manager.list_blackouts(request.resource_id).await?;
+        Ok(Response::new(ListBlackoutsResponse { blackouts }))
     }
 }
 
+/// expected version of the request, 0 means any version
+fn expected_version(version: i64) -> Option<i64> {
+    (version != 0).then_some(version)
+}
+
+/// pair the batch results with the ids they were returned for
+fn to_results(ids: Vec<i64>, results: Vec<BatchResult>) -> Vec<ReservationResult> {
+    ids.into_iter()
+        .zip(results)
+        .map(|(id, result)| ReservationResult::new(id, result))
+        .collect()
+}
+
 impl<T> TonicReceiverStream<T> {
     pub fn new(inner: mpsc::Receiver<Result<T, abi::Error>>) -> Self {
         Self { inner }
@@ -146,7 +493,7 @@ impl<T> Stream for TonicReceiverStream<T> {
 mod tests {
     use super::*;
 
-    use abi::{DbConfig, Reservation};
+    use abi::{DbConfig, Reservation, Resource};
     use docker_tester::TestPostgres;
 
     #[tokio::test]
@@ -164,6 +511,10 @@ mod tests {
             ..Default::default()
         };
         let service = RsvpService::from_config(&config).await.unwrap();
+        let request = tonic::Request::new(CreateResourceRequest {
+            resource: Some(Resource::new("ixia-3228", "IXIA 3228", "device")),
+        });
+        service.create_resource(request).await.unwrap();
         let reservation = Reservation::new_pending(
             "ben",
             "ixia-3228",
@@ -171,10 +522,11 @@ mod tests {
             "2022-12-30T12:00:00-0700".parse().unwrap(),
             "test device reservation",
         );
-        let request = tonic::Request::new(ReserveRequest {
-            reservation: Some(reservation.clone()),
-        });
-        let response = service.reserve(request).await.unwrap();
+        let request = ReserveRequest::new(reservation.clone()).with_idempotency_key("ben-1");
+        let response = service
+            .reserve(tonic::Request::new(request.clone()))
+            .await
+            .unwrap();
         let reservation_resp = response.into_inner().reservation;
         assert!(reservation_resp.is_some());
         let insert_reservation = reservation_resp.unwrap();
@@ -185,5 +537,9 @@ mod tests {
         assert_eq!(insert_reservation.start, reservation.start);
         assert_eq!(insert_reservation.end, reservation.end);
         assert_eq!(insert_reservation.note, reservation.note);
+
+        // a retry with the same key returns the same reservation instead of a conflict
+        let response = service.reserve(tonic::Request::new(request)).await.unwrap();
+        assert_eq!(response.into_inner().reservation, Some(insert_reservation));
     }
 }
@@ -1,20 +1,42 @@
 use std::time::Duration;
 
 use abi::{
-    reservation_service_client::ReservationServiceClient, Config, ConfirmRequest, DbConfig,
-    FilterRequest, FilterResponse, QueryRequest, Reservation, ReservationFilterBuilder,
-    ReservationQueryBuilder, ReservationStatus, ReserveRequest, ServerConfig,
+    reservation_service_client::ReservationServiceClient, CancelRequest, Config, ConfirmRequest,
+    ConflictDetail, CreateResourceRequest, DbConfig, ErrorCode, FilterRequest, FilterResponse,
+    ListenRequest, QueryRequest, Reservation, ReservationFilterBuilder, ReservationQueryBuilder,
+    ReservationStatus, ReservationUpdateType, ReserveAnyRequest, ReserveRequest, Resource,
+    ServerConfig,
 };
 use docker_tester::TestPostgres;
 use reservation_service::start_server;
 use tokio::time;
 use tokio_stream::StreamExt;
-use tonic::transport::Channel;
+use tonic::{transport::Channel, Code};
 
 #[tokio::test]
 async fn grpc_server_should_work() {
     let test_app = TestPostgres::new("../migrations").await.unwrap();
     let mut client = get_test_client(&test_app, 50051).await;
+    // reservations could only be made on resources in the catalog
+    let room = Resource::new("ocean-view-room-713", "Ocean View 713", "room");
+    let status = client
+        .reserve(ReserveRequest::new(Reservation::new_pending(
+            "user_id_1",
+            "ocean-view-room-713",
+            "2022-12-25T15:00:00-0700".parse().unwrap(),
+            "2022-12-28T12:00:00-0700".parse().unwrap(),
+            "hello I'm user 1.",
+        )))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::NotFound);
+    client
+        .create_resource(CreateResourceRequest {
+            resource: Some(room.clone()),
+        })
+        .await
+        .unwrap();
+
     // first we make a reservation
     let mut rsvp = Reservation::new_pending(
         "user_id_1",
@@ -33,6 +55,7 @@ async fn grpc_server_should_work() {
         .unwrap();
     let confirm_rsvp_id = ret.id;
     rsvp.id = ret.id;
+    rsvp.version = 1;
     assert_eq!(ret, rsvp);
 
     // then we try to make a conflicting reservation
@@ -59,6 +82,83 @@ async fn grpc_server_should_work() {
     );
 }
 
+#[tokio::test]
+async fn grpc_conflict_should_return_details() {
+    let test_app = TestPostgres::new("../migrations").await.unwrap();
+    let mut client = get_test_client(&test_app, 50055).await;
+    create_resource(&mut client, "ocean-view-room-713").await;
+    let rsvp = make_reservation(&mut client, "ocean-view-room-713").await;
+
+    let status = client
+        .reserve(ReserveRequest::new(Reservation::new_pending(
+            "user_id_2",
+            "ocean-view-room-713",
+            "2022-12-26T15:00:00-0700".parse().unwrap(),
+            "2022-12-27T12:00:00-0700".parse().unwrap(),
+            "hello I'm user 2.",
+        )))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::FailedPrecondition);
+    let detail = ConflictDetail::from_status(&status).unwrap();
+    assert_eq!(detail.code, ErrorCode::ReservationConflict as i32);
+    assert_eq!(detail.conflicting_id, rsvp.id);
+    assert_eq!(detail.old.unwrap().start, rsvp.start);
+}
+
+#[tokio::test]
+async fn grpc_confirm_with_stale_version_should_fail() {
+    let test_app = TestPostgres::new("../migrations").await.unwrap();
+    let mut client = get_test_client(&test_app, 50056).await;
+    create_resource(&mut client, "ocean-view-room-713").await;
+    let rsvp = make_reservation(&mut client, "ocean-view-room-713").await;
+
+    let status = client
+        .confirm(ConfirmRequest::new(rsvp.id).with_expected_version(rsvp.version + 1))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::FailedPrecondition);
+
+    let ret = client
+        .confirm(ConfirmRequest::new(rsvp.id).with_expected_version(rsvp.version))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(
+        ret.reservation.unwrap().status,
+        ReservationStatus::Confirmed as i32
+    );
+}
+
+#[tokio::test]
+async fn grpc_reserve_any_should_pick_free_resource() {
+    let test_app = TestPostgres::new("../migrations").await.unwrap();
+    let mut client = get_test_client(&test_app, 50057).await;
+    create_resource(&mut client, "ocean-view-room-713").await;
+    create_resource(&mut client, "ocean-view-room-714").await;
+    make_reservation(&mut client, "ocean-view-room-713").await;
+
+    // the first room is taken, so the second one gets the reservation
+    let ret = client
+        .reserve_any(ReserveAnyRequest {
+            reservation: Some(Reservation::new_pending(
+                "user_id_2",
+                "",
+                "2022-12-25T15:00:00-0700".parse().unwrap(),
+                "2022-12-28T12:00:00-0700".parse().unwrap(),
+                "hello I'm user 2.",
+            )),
+            resource_ids: vec!["ocean-view-room-713".into(), "ocean-view-room-714".into()],
+            idempotency_key: String::new(),
+        })
+        .await
+        .unwrap()
+        .into_inner()
+        .reservation
+        .unwrap();
+    assert_eq!(ret.resource_id, "ocean-view-room-714");
+}
+
 #[tokio::test]
 async fn grpc_query_should_work() {
     let test_app = TestPostgres::new("../migrations").await.unwrap();
@@ -148,6 +248,38 @@ async fn grpc_filter_should_work() {
     assert_eq!(reservations.len(), 5);
 }
 
+#[tokio::test]
+async fn grpc_listen_should_work() {
+    let test_app = TestPostgres::new("../migrations").await.unwrap();
+    let mut client = get_test_client(&test_app, 50054).await;
+    let mut stream = client
+        .listen(ListenRequest::default())
+        .await
+        .unwrap()
+        .into_inner();
+
+    make_reservations(&mut client, 3, "listen_user").await;
+    let mut rsvps = Vec::new();
+    for _ in 0..3 {
+        let change = stream.next().await.unwrap().unwrap();
+        assert_eq!(change.op, ReservationUpdateType::Create as i32);
+        let rsvp = change.reservation.unwrap();
+        assert_eq!(rsvp.user_id, "listen_user");
+        rsvps.push(rsvp);
+    }
+
+    client
+        .cancel(CancelRequest::new(rsvps[0].id).with_reason("no longer needed"))
+        .await
+        .unwrap();
+    let change = stream.next().await.unwrap().unwrap();
+    assert_eq!(change.op, ReservationUpdateType::Update as i32);
+    let rsvp = change.reservation.unwrap();
+    assert_eq!(rsvp.id, rsvps[0].id);
+    assert_eq!(rsvp.status, ReservationStatus::Cancelled as i32);
+    assert_eq!(rsvp.cancel_reason, "no longer needed");
+}
+
 async fn get_test_client(test_app: &TestPostgres, port: u16) -> ReservationServiceClient<Channel> {
     let config = Config {
         db: DbConfig {
@@ -162,6 +294,7 @@ async fn get_test_client(test_app: &TestPostgres, port: u16) -> ReservationServi
             host: "0.0.0.0".into(),
             port,
         },
+        ..Default::default()
     };
     setup_server(&config);
 
@@ -188,12 +321,47 @@ fn setup_server(config: &Config) {
     });
 }
 
+async fn create_resource(client: &mut ReservationServiceClient<Channel>, id: &str) {
+    client
+        .create_resource(CreateResourceRequest {
+            resource: Some(Resource::new(id, id, "room")),
+        })
+        .await
+        .unwrap();
+}
+
+async fn make_reservation(
+    client: &mut ReservationServiceClient<Channel>,
+    resource_id: &str,
+) -> Reservation {
+    client
+        .reserve(ReserveRequest::new(Reservation::new_pending(
+            "user_id_1",
+            resource_id,
+            "2022-12-25T15:00:00-0700".parse().unwrap(),
+            "2022-12-28T12:00:00-0700".parse().unwrap(),
+            "hello I'm user 1.",
+        )))
+        .await
+        .unwrap()
+        .into_inner()
+        .reservation
+        .unwrap()
+}
+
 async fn make_reservations(
     client: &mut ReservationServiceClient<Channel>,
     count: usize,
     uid: &str,
 ) {
     for i in 0..count {
+        let resource = Resource::new(format!("router-{i}"), format!("Router {i}"), "device");
+        client
+            .create_resource(CreateResourceRequest {
+                resource: Some(resource),
+            })
+            .await
+            .unwrap();
         let mut rsvp = Reservation::new_pending(
             uid,
             format!("router-{i}"),
@@ -210,6 +378,7 @@ async fn make_reservations(
             .reservation
             .unwrap();
         rsvp.id = ret.id;
+        rsvp.version = 1;
         assert_eq!(ret, rsvp);
     }
 }